@@ -0,0 +1,12 @@
+//! Utilitários compartilhados pelos benchmarks.
+
+use std::time::{Duration, Instant};
+
+/// Tempo médio de `operacao` ao longo de `iteracoes` execuções.
+pub fn medir<F: FnMut()>(iteracoes: u32, mut operacao: F) -> Duration {
+    let inicio = Instant::now();
+    for _ in 0..iteracoes {
+        operacao();
+    }
+    inicio.elapsed() / iteracoes
+}
@@ -0,0 +1,99 @@
+//! Compara o crivo incremental com a divisão por tentativa ingênua, primeiro
+//! só na filtragem de candidatos e depois na busca completa por um primo.
+//!
+//! Executar com `cargo bench --bench crivo`.
+
+mod comum;
+
+use comum::medir;
+use num_bigint::{BigInt, RandBigInt};
+use rsa_simulado::primes::crivo::{divisao_por_tentativa, CrivoIncremental};
+use rsa_simulado::primes::{eh_primo, gerar_primo, RODADAS_MILLER_RABIN};
+use std::hint::black_box;
+
+const ITERACOES: u32 = 10;
+
+/// Candidatos ímpares consecutivos examinados na comparação de filtragem.
+const JANELA_FILTRAGEM: u64 = 4096;
+
+/// Busca ingênua: sorteia candidatos independentes, cada um dividido por
+/// todos os primos do crivo antes do Miller–Rabin.
+fn gerar_primo_ingenuo(bits: u64) -> BigInt {
+    let mut rng = rand::thread_rng();
+    loop {
+        let mut candidato = BigInt::from(rng.gen_biguint(bits));
+        candidato.set_bit(bits - 1, true);
+        candidato.set_bit(bits - 2, true);
+        candidato.set_bit(0, true);
+        if divisao_por_tentativa(&candidato) == Some(false) {
+            continue;
+        }
+        if eh_primo(&candidato, RODADAS_MILLER_RABIN) {
+            return candidato;
+        }
+    }
+}
+
+fn main() {
+    let mut rng = rand::thread_rng();
+
+    println!(
+        "--- Filtragem de {} candidatos ímpares: divisão por tentativa x crivo incremental ---",
+        JANELA_FILTRAGEM
+    );
+
+    for bits in [512u64, 1024, 2048] {
+        let mut base = BigInt::from(rng.gen_biguint(bits));
+        base.set_bit(bits - 1, true);
+        base.set_bit(0, true);
+        let limite = &base + 2 * JANELA_FILTRAGEM;
+
+        // A concordância entre os dois métodos é conferida nos testes de `primes::crivo`
+        let sobreviventes_crivo = CrivoIncremental::new(&base)
+            .expect("base positiva")
+            .take_while(|candidato| *candidato < limite)
+            .count();
+
+        let ingenuo = medir(ITERACOES, || {
+            for i in 0..JANELA_FILTRAGEM {
+                black_box(divisao_por_tentativa(&(&base + 2 * i)));
+            }
+        });
+        let crivo = medir(ITERACOES, || {
+            black_box(
+                CrivoIncremental::new(&base)
+                    .expect("base positiva")
+                    .take_while(|candidato| *candidato < limite)
+                    .count(),
+            );
+        });
+
+        println!(
+            "  > {} bits: {} sobreviventes | tentativa: {:?} | crivo: {:?} | ganho: {:.1}x",
+            bits,
+            sobreviventes_crivo,
+            ingenuo,
+            crivo,
+            ingenuo.as_secs_f64() / crivo.as_secs_f64()
+        );
+    }
+
+    println!("--- Busca de um primo: candidatos independentes x crivo incremental ---");
+
+    for bits in [512u32, 1024] {
+        let ingenuo = medir(ITERACOES, || {
+            black_box(gerar_primo_ingenuo(u64::from(bits)));
+        });
+        let crivo = medir(ITERACOES, || {
+            black_box(gerar_primo(bits).expect("falha ao gerar o primo"));
+        });
+
+        println!(
+            "  > {} bits: ingênua: {:?} | crivo: {:?} | ganho: {:.1}x",
+            bits,
+            ingenuo,
+            crivo,
+            ingenuo.as_secs_f64() / crivo.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,44 @@
+//! Compara a descriptografia pelo CRT com a exponenciação direta c^d mod n.
+//!
+//! Executar com `cargo bench --bench crt`.
+
+mod comum;
+
+use comum::medir;
+use num_bigint::{BigInt, RandBigInt};
+use rsa_simulado::keys::{KeyPair, EXPOENTE_PUBLICO_PADRAO};
+use std::hint::black_box;
+
+const ITERACOES: u32 = 50;
+
+fn main() {
+    let e = BigInt::from(EXPOENTE_PUBLICO_PADRAO);
+    let mut rng = rand::thread_rng();
+
+    println!("--- Descriptografia: CRT x exponenciação direta ---");
+
+    for bits in [1024, 2048] {
+        let chaves = KeyPair::generate(bits, &e).expect("falha ao gerar as chaves");
+        let c = rng.gen_bigint_range(&BigInt::from(0), &chaves.publica.n);
+
+        let direta = medir(ITERACOES, || {
+            black_box(
+                chaves
+                    .privada
+                    .aplicar_expoente_privado_sem_crt(black_box(&c)),
+            )
+            .unwrap();
+        });
+        let crt = medir(ITERACOES, || {
+            black_box(chaves.privada.aplicar_expoente_privado(black_box(&c))).unwrap();
+        });
+
+        println!(
+            "  > {} bits: direta {:?}, CRT {:?} (ganho de {:.2}x)",
+            bits,
+            direta,
+            crt,
+            direta.as_secs_f64() / crt.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Compara o square-and-multiply binário com as janelas fixa e deslizante
+//! para expoentes de 1024, 2048 e 4096 bits.
+//!
+//! Executar com `cargo bench --bench janelas`.
+
+mod comum;
+
+use comum::medir;
+use num_bigint::{BigInt, RandBigInt};
+use rsa_simulado::arithmetic::{
+    exponenciacao_modular_com_metodo, exponenciacao_modular_referencia, MetodoExponenciacao,
+};
+use std::hint::black_box;
+
+const ITERACOES: u32 = 20;
+
+fn main() {
+    let mut rng = rand::thread_rng();
+
+    println!("--- Exponenciação modular: binária x janelas ---");
+
+    for bits in [1024u64, 2048, 4096] {
+        let mut modulo = BigInt::from(rng.gen_biguint(bits));
+        modulo.set_bit(bits - 1, true);
+        modulo.set_bit(0, true);
+        let base = rng.gen_bigint_range(&BigInt::from(0), &modulo);
+        let mut exp = BigInt::from(rng.gen_biguint(bits));
+        exp.set_bit(bits - 1, true);
+        let esperado = exponenciacao_modular_referencia(&base, &exp, &modulo).unwrap();
+
+        println!("  > expoente de {} bits:", bits);
+        let mut metodos = vec![MetodoExponenciacao::Binario];
+        for k in 3..=6 {
+            metodos.push(MetodoExponenciacao::JanelaFixa(k));
+        }
+        for k in 3..=7 {
+            metodos.push(MetodoExponenciacao::JanelaDeslizante(k));
+        }
+
+        let mut binario = None;
+        for metodo in metodos {
+            assert_eq!(
+                exponenciacao_modular_com_metodo(&base, &exp, &modulo, metodo).unwrap(),
+                esperado
+            );
+            let tempo = medir(ITERACOES, || {
+                black_box(exponenciacao_modular_com_metodo(
+                    black_box(&base),
+                    black_box(&exp),
+                    &modulo,
+                    metodo,
+                ))
+                .unwrap();
+            });
+            let referencia = *binario.get_or_insert(tempo);
+            println!(
+                "      {:?}: {:?} ({:.2}x)",
+                metodo,
+                tempo,
+                referencia.as_secs_f64() / tempo.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Compara a exponenciação modular de Montgomery com a versão de referência,
+//! que reduz por `%` após cada produto.
+//!
+//! Executar com `cargo bench --bench montgomery`.
+
+mod comum;
+
+use comum::medir;
+use num_bigint::{BigInt, RandBigInt};
+use rsa_simulado::arithmetic::montgomery::ContextoMontgomery;
+use rsa_simulado::arithmetic::{
+    exponenciacao_modular_com_metodo, exponenciacao_modular_referencia, MetodoExponenciacao,
+};
+use std::hint::black_box;
+
+const ITERACOES: u32 = 20;
+
+fn main() {
+    let mut rng = rand::thread_rng();
+
+    println!("--- Exponenciação modular: referência x Montgomery ---");
+
+    for bits in [1024u64, 2048, 4096] {
+        // Módulo ímpar com exatamente `bits` bits
+        let mut modulo = BigInt::from(rng.gen_biguint(bits));
+        modulo.set_bit(bits - 1, true);
+        modulo.set_bit(0, true);
+        let base = rng.gen_bigint_range(&BigInt::from(0), &modulo);
+        let exp = rng.gen_bigint_range(&BigInt::from(0), &modulo);
+        assert_eq!(
+            exponenciacao_modular_com_metodo(&base, &exp, &modulo, MetodoExponenciacao::Binario)
+                .unwrap(),
+            exponenciacao_modular_referencia(&base, &exp, &modulo).unwrap()
+        );
+
+        let referencia = medir(ITERACOES, || {
+            black_box(exponenciacao_modular_referencia(
+                black_box(&base),
+                black_box(&exp),
+                &modulo,
+            ))
+            .unwrap();
+        });
+        let montgomery = medir(ITERACOES, || {
+            black_box(exponenciacao_modular_com_metodo(
+                black_box(&base),
+                black_box(&exp),
+                &modulo,
+                MetodoExponenciacao::Binario,
+            ))
+            .unwrap();
+        });
+        let contexto = ContextoMontgomery::new(&modulo).unwrap();
+        let pre_calculado = medir(ITERACOES, || {
+            black_box(contexto.exponenciar(black_box(&base), black_box(&exp)));
+        });
+
+        println!(
+            "  > {} bits: referência {:?}, Montgomery {:?} (ganho de {:.2}x), contexto pré-calculado {:?}",
+            bits,
+            referencia,
+            montgomery,
+            referencia.as_secs_f64() / montgomery.as_secs_f64(),
+            pre_calculado
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Compara a descriptografia pelo CRT de chaves com dois primos e de chaves
+//! multiprimo (RFC 8017) do mesmo tamanho.
+//!
+//! Executar com `cargo bench --bench multiprimo`.
+
+mod comum;
+
+use comum::medir;
+use num_bigint::{BigInt, RandBigInt};
+use rsa_simulado::keys::{
+    quantidade_maxima_primos, KeyPair, OpcoesGeracao, EXPOENTE_PUBLICO_PADRAO,
+};
+use std::hint::black_box;
+
+const ITERACOES: u32 = 50;
+
+fn main() {
+    let e = BigInt::from(EXPOENTE_PUBLICO_PADRAO);
+    let mut rng = rand::thread_rng();
+
+    println!("--- Descriptografia: dois primos x multiprimo ---");
+
+    for bits in [2048, 4096] {
+        println!("  > {} bits:", bits);
+        let mut referencia = None;
+        for quantidade_primos in 2..=quantidade_maxima_primos(bits) {
+            let opcoes = OpcoesGeracao {
+                quantidade_primos,
+                ..Default::default()
+            };
+            let chaves =
+                KeyPair::generate_com_opcoes(bits, &e, &opcoes).expect("falha ao gerar as chaves");
+            let c = rng.gen_bigint_range(&BigInt::from(0), &chaves.publica.n);
+
+            let tempo = medir(ITERACOES, || {
+                black_box(chaves.privada.aplicar_expoente_privado(black_box(&c))).unwrap();
+            });
+            let dois_primos = *referencia.get_or_insert(tempo);
+
+            println!(
+                "      {} primos: {:?} (ganho de {:.2}x)",
+                quantidade_primos,
+                tempo,
+                dois_primos.as_secs_f64() / tempo.as_secs_f64()
+            );
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Estatísticas de tempo da geração de primos aleatórios, seguros e fortes.
+//!
+//! Executar com `cargo bench --bench primos_especiais`.
+
+use rsa_simulado::primes::especiais::{medir_geracao, TipoPrimo};
+
+const AMOSTRAS: u32 = 10;
+
+fn main() {
+    println!("--- Geração de primos: aleatórios x seguros x fortes ---");
+
+    for bits in [256, 512] {
+        println!("  > {} bits:", bits);
+        for tipo in [TipoPrimo::Aleatorio, TipoPrimo::Seguro, TipoPrimo::Forte] {
+            let estatisticas =
+                medir_geracao(tipo, bits, AMOSTRAS).expect("falha ao gerar os primos");
+            println!("      {:?}: {}", tipo, estatisticas);
+        }
+    }
+}
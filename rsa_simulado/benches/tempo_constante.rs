@@ -0,0 +1,112 @@
+//! Teste estatístico de vazamento por tempo no estilo do dudect
+//! (Reparaz, Balasch e Verbauwhede, 2017).
+//!
+//! Cada medição sorteia uma de duas classes de expoente secreto: fixo, com
+//! apenas dois bits 1, ou aleatório com o mesmo tamanho. Se o tempo da
+//! exponenciação não depende do expoente, as duas distribuições coincidem e a
+//! estatística t de Welch fica próxima de zero; |t| > 4,5 indica vazamento.
+//!
+//! Executar com `cargo bench --bench tempo_constante`.
+
+use num_bigint::{BigInt, RandBigInt};
+use rand::Rng;
+use rsa_simulado::arithmetic::montgomery::ContextoMontgomery;
+use rsa_simulado::arithmetic::{exponenciacao_modular, exponenciacao_modular_tempo_constante};
+use std::hint::black_box;
+use std::time::Instant;
+
+const BITS: u64 = 512;
+const MEDICOES: usize = 10_000;
+
+/// Limite usual do dudect acima do qual o vazamento é considerado detectado.
+const LIMITE_T: f64 = 4.5;
+
+/// Estatística t de Welch entre as duas amostras.
+fn t_de_welch(a: &[f64], b: &[f64]) -> f64 {
+    let media = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+    let variancia =
+        |x: &[f64], m: f64| x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (x.len() - 1) as f64;
+    let (ma, mb) = (media(a), media(b));
+    let (va, vb) = (variancia(a, ma), variancia(b, mb));
+    (ma - mb) / (va / a.len() as f64 + vb / b.len() as f64).sqrt()
+}
+
+/// Mede `operacao` sobre entradas das duas classes, sorteadas a cada passo, e
+/// devolve |t| após descartar os 5% de medições mais lentas (ruído do sistema).
+fn testar<F: Fn(&BigInt, &BigInt) -> BigInt>(operacao: F, modulo: &BigInt, fixo: &BigInt) -> f64 {
+    let mut rng = rand::thread_rng();
+    let mut medicoes = Vec::with_capacity(MEDICOES);
+
+    for _ in 0..MEDICOES {
+        let classe = rng.gen_bool(0.5);
+        let base = rng.gen_bigint_range(&BigInt::from(2), modulo);
+        let exp = if classe {
+            let mut aleatorio = BigInt::from(rng.gen_biguint(BITS));
+            aleatorio.set_bit(BITS - 1, true);
+            aleatorio
+        } else {
+            fixo.clone()
+        };
+
+        let inicio = Instant::now();
+        black_box(operacao(black_box(&base), black_box(&exp)));
+        medicoes.push((classe, inicio.elapsed().as_nanos() as f64));
+    }
+
+    let mut tempos: Vec<f64> = medicoes.iter().map(|&(_, tempo)| tempo).collect();
+    tempos.sort_by(f64::total_cmp);
+    let corte = tempos[tempos.len() * 95 / 100];
+
+    let classe = |alvo: bool| -> Vec<f64> {
+        medicoes
+            .iter()
+            .filter(|&&(c, tempo)| c == alvo && tempo <= corte)
+            .map(|&(_, tempo)| tempo)
+            .collect()
+    };
+    t_de_welch(&classe(false), &classe(true)).abs()
+}
+
+fn main() {
+    let mut rng = rand::thread_rng();
+    let mut modulo = BigInt::from(rng.gen_biguint(BITS));
+    modulo.set_bit(BITS - 1, true);
+    modulo.set_bit(0, true);
+
+    // Expoente fixo de peso de Hamming 2: 2^(BITS−1) + 1
+    let mut fixo = BigInt::from(1);
+    fixo.set_bit(BITS - 1, true);
+
+    println!(
+        "--- Vazamento por tempo (dudect, {} medições, módulo de {} bits) ---",
+        MEDICOES, BITS
+    );
+
+    let variavel = testar(
+        |b, e| exponenciacao_modular(b, e, &modulo).unwrap(),
+        &modulo,
+        &fixo,
+    );
+    let contexto = ContextoMontgomery::new(&modulo).unwrap();
+    let escada = testar(
+        |b, e| contexto.exponenciar_tempo_constante(b, e),
+        &modulo,
+        &fixo,
+    );
+    assert_eq!(
+        exponenciacao_modular_tempo_constante(&fixo, &modulo, &modulo).unwrap(),
+        exponenciacao_modular(&fixo, &modulo, &modulo).unwrap()
+    );
+
+    for (nome, t) in [
+        ("exponenciacao_modular", variavel),
+        ("escada de Montgomery", escada),
+    ] {
+        let veredito = if t > LIMITE_T {
+            "vazamento detectado"
+        } else {
+            "sem evidência de vazamento"
+        };
+        println!("  > {}: |t| = {:.2} ({})", nome, t, veredito);
+    }
+}
@@ -0,0 +1,442 @@
+// --------------------------------------------------------
+// Funções Matemáticas Essenciais (BigInt)
+// --------------------------------------------------------
+
+pub mod montgomery;
+
+use crate::error::RsaError;
+use montgomery::ContextoMontgomery;
+use num::Integer;
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+use std::ops::Shr;
+
+/// Algoritmo Euclidiano Estendido, na forma iterativa.
+/// Retorna (gcd, x, y) tal que a*x + b*y = gcd(a, b), com gcd ≥ 0 mesmo
+/// para operandos negativos.
+///
+/// Mantém apenas os dois últimos restos e coeficientes de Bézout, então a
+/// memória e a pilha não crescem com o número de passos.
+pub fn algoritmo_euclidiano_estendido(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut r_anterior, mut r) = (a.clone(), b.clone());
+    let (mut x_anterior, mut x) = (BigInt::one(), BigInt::zero());
+    let (mut y_anterior, mut y) = (BigInt::zero(), BigInt::one());
+
+    // Invariantes: a*x_anterior + b*y_anterior = r_anterior e a*x + b*y = r
+    while !r.is_zero() {
+        let (quociente, resto) = r_anterior.div_rem(&r);
+        r_anterior = std::mem::replace(&mut r, resto);
+
+        let proximo_x = &x_anterior - &quociente * &x;
+        x_anterior = std::mem::replace(&mut x, proximo_x);
+        let proximo_y = &y_anterior - &quociente * &y;
+        y_anterior = std::mem::replace(&mut y, proximo_y);
+    }
+
+    // A divisão trunca em direção a zero, então o último resto pode ser negativo
+    if r_anterior.is_negative() {
+        return (-r_anterior, -x_anterior, -y_anterior);
+    }
+    (r_anterior, x_anterior, y_anterior)
+}
+
+/// Máximo divisor comum pelo algoritmo binário de Stein.
+///
+/// Troca as divisões do Euclides por deslocamentos e subtrações: remove os
+/// fatores 2 comuns, depois subtrai repetidamente o menor ímpar do maior.
+/// O resultado é sempre não negativo.
+pub fn mdc_binario(a: &BigInt, b: &BigInt) -> BigInt {
+    let mut a = a.abs();
+    let mut b = b.abs();
+    if a.is_zero() {
+        return b;
+    }
+    if b.is_zero() {
+        return a;
+    }
+
+    let zeros_a = a.trailing_zeros().unwrap_or(0);
+    let zeros_b = b.trailing_zeros().unwrap_or(0);
+    let fatores_comuns = zeros_a.min(zeros_b);
+    a >>= zeros_a;
+
+    // Invariante: a é ímpar
+    loop {
+        b >>= b.trailing_zeros().unwrap_or(0);
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= &a;
+        if b.is_zero() {
+            return a << fatores_comuns;
+        }
+    }
+}
+
+/// Inverso modular de `e` módulo `phi_n`, isto é, d tal que e * d ≡ 1 (mod φ(n)).
+/// Retorna `RsaError::NotInvertible` quando gcd(e, φ(n)) ≠ 1.
+pub fn inverso_modular(e: &BigInt, phi_n: &BigInt) -> Result<BigInt, RsaError> {
+    if *phi_n <= BigInt::one() {
+        return Err(RsaError::NotInvertible);
+    }
+
+    let (gcd, x, _) = algoritmo_euclidiano_estendido(&e.mod_floor(phi_n), phi_n);
+    if !gcd.is_one() {
+        return Err(RsaError::NotInvertible);
+    }
+    Ok(x.mod_floor(phi_n))
+}
+
+/// Estratégia de varredura do expoente em `exponenciacao_modular_com_metodo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetodoExponenciacao {
+    /// Um bit por vez (*square-and-multiply*).
+    Binario,
+    /// Método *k-ário*: dígitos de `k` bits e tabela com 2^k potências.
+    JanelaFixa(u32),
+    /// Janelas de até `k` bits começando em bits 1, com tabela de 2^(k−1)
+    /// potências ímpares.
+    JanelaDeslizante(u32),
+}
+
+impl MetodoExponenciacao {
+    /// Janela deslizante com o tamanho sugerido para um expoente de `bits` bits.
+    pub fn recomendado(bits: u64) -> Self {
+        MetodoExponenciacao::JanelaDeslizante(tamanho_janela_recomendado(bits))
+    }
+}
+
+/// Tamanho de janela que minimiza o número de produtos para um expoente de
+/// `bits` bits (mesmos limiares do OpenSSL).
+pub fn tamanho_janela_recomendado(bits: u64) -> u32 {
+    match bits {
+        672.. => 6,
+        240..=671 => 5,
+        80..=239 => 4,
+        24..=79 => 3,
+        _ => 1,
+    }
+}
+
+/// Exponenciação modular: base^exp mod modulo.
+///
+/// Usa a janela deslizante com o tamanho de `tamanho_janela_recomendado`.
+/// Retorna `RsaError::InvalidKey` se `modulo <= 0` ou `exp < 0`.
+pub fn exponenciacao_modular(
+    base: &BigInt,
+    exp: &BigInt,
+    modulo: &BigInt,
+) -> Result<BigInt, RsaError> {
+    let metodo = MetodoExponenciacao::recomendado(exp.bits());
+    exponenciacao_modular_com_metodo(base, exp, modulo, metodo)
+}
+
+/// base^exp mod modulo com o método de varredura escolhido pelo chamador.
+///
+/// Módulos ímpares (como n, p e q) usam a multiplicação de Montgomery; os
+/// demais recaem em `exponenciacao_modular_referencia`, qualquer que seja o
+/// método. Janelas fora de [1, 8] são ajustadas para esse intervalo.
+///
+/// Retorna `RsaError::InvalidKey` se `modulo <= 0` ou `exp < 0`.
+pub fn exponenciacao_modular_com_metodo(
+    base: &BigInt,
+    exp: &BigInt,
+    modulo: &BigInt,
+    metodo: MetodoExponenciacao,
+) -> Result<BigInt, RsaError> {
+    let contexto = match ContextoMontgomery::new(modulo) {
+        Ok(contexto) => contexto,
+        Err(_) => return exponenciacao_modular_referencia(base, exp, modulo),
+    };
+    validar_operandos(exp, modulo)?;
+    Ok(match metodo {
+        MetodoExponenciacao::Binario => contexto.exponenciar(base, exp),
+        MetodoExponenciacao::JanelaFixa(k) => contexto.exponenciar_janela_fixa(base, exp, k),
+        MetodoExponenciacao::JanelaDeslizante(k) => {
+            contexto.exponenciar_janela_deslizante(base, exp, k)
+        }
+    })
+}
+
+/// base^exp mod modulo em tempo constante no expoente (escada de Montgomery).
+///
+/// Deve ser usada sempre que `exp` for secreto (d, dp, dq). Retorna
+/// `RsaError::InvalidKey` se `modulo <= 0` ou `exp < 0` e
+/// `RsaError::NotInvertible` se `modulo` não for ímpar e maior que 1.
+pub fn exponenciacao_modular_tempo_constante(
+    base: &BigInt,
+    exp: &BigInt,
+    modulo: &BigInt,
+) -> Result<BigInt, RsaError> {
+    validar_operandos(exp, modulo)?;
+    let contexto = ContextoMontgomery::new(modulo)?;
+    Ok(contexto.exponenciar_tempo_constante(base, exp))
+}
+
+/// Exponenciação modular rápida (*square-and-multiply*) com redução por `%`
+/// após cada produto. Mantida como referência para conferir e comparar a
+/// versão de Montgomery.
+///
+/// Retorna `RsaError::InvalidKey` se `modulo <= 0` ou `exp < 0`.
+pub fn exponenciacao_modular_referencia(
+    base: &BigInt,
+    exp: &BigInt,
+    modulo: &BigInt,
+) -> Result<BigInt, RsaError> {
+    validar_operandos(exp, modulo)?;
+    // `mod_floor` em vez de `%`: o resultado fica em [0, modulo) mesmo com
+    // base negativa ou módulo 1
+    let mut resultado = BigInt::one().mod_floor(modulo);
+    let mut base = base.mod_floor(modulo);
+    let mut exp = exp.clone();
+
+    while exp > BigInt::from(0) {
+        if &exp % BigInt::from(2) == BigInt::from(1) {
+            resultado = (resultado * &base) % modulo;
+        }
+        exp = exp.shr(1);
+        base = (&base * &base) % modulo;
+    }
+    Ok(resultado)
+}
+
+/// Exige `modulo > 0` e `exp >= 0`, sem os quais a exponenciação não é definida.
+fn validar_operandos(exp: &BigInt, modulo: &BigInt) -> Result<(), RsaError> {
+    if *modulo <= BigInt::from(0) || *exp < BigInt::from(0) {
+        return Err(RsaError::InvalidKey);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::RandBigInt;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Pares de 2000+ bits sorteados com semente fixa, com sinais aleatórios,
+    /// fatores comuns (inclusive potências de 2) e os casos com zero.
+    fn pares_de_teste() -> Vec<(BigInt, BigInt)> {
+        let mut rng = StdRng::seed_from_u64(17);
+        let mut pares = Vec::new();
+        for _ in 0..64 {
+            let (bits_a, bits_b) = (rng.gen_range(2000..2600), rng.gen_range(2000..2600));
+            let (a, b) = (rng.gen_bigint(bits_a), rng.gen_bigint(bits_b));
+            let (bits_comum, deslocamento) = (rng.gen_range(1..600), rng.gen_range(0..40));
+            let comum = rng.gen_bigint(bits_comum) << deslocamento;
+            pares.push((&a * &comum, &b * &comum));
+            pares.push((a, b));
+        }
+
+        let grande = rng.gen_bigint(2048);
+        for (a, b) in [
+            (BigInt::zero(), BigInt::zero()),
+            (BigInt::zero(), grande.clone()),
+            (-&grande, BigInt::zero()),
+            (grande.clone(), grande.clone()),
+            (-&grande, grande.clone()),
+            (BigInt::one() << 2048, BigInt::one() << 2001),
+        ] {
+            pares.push((a, b));
+        }
+        pares
+    }
+
+    #[test]
+    fn euclides_estendido_satisfaz_a_identidade_de_bezout() {
+        for (a, b) in pares_de_teste() {
+            let (gcd, x, y) = algoritmo_euclidiano_estendido(&a, &b);
+            assert_eq!(gcd, a.gcd(&b), "a = {}, b = {}", a, b);
+            assert_eq!(&a * &x + &b * &y, gcd, "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn mdc_binario_concorda_com_integer_gcd() {
+        for (a, b) in pares_de_teste() {
+            assert_eq!(mdc_binario(&a, &b), a.gcd(&b), "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn exponenciacao_rejeita_modulo_nao_positivo_e_expoente_negativo() {
+        let (base, exp) = (BigInt::from(5), BigInt::from(3));
+        for modulo in [BigInt::zero(), BigInt::from(-7)] {
+            assert_eq!(
+                exponenciacao_modular(&base, &exp, &modulo),
+                Err(RsaError::InvalidKey)
+            );
+            assert_eq!(
+                exponenciacao_modular_referencia(&base, &exp, &modulo),
+                Err(RsaError::InvalidKey)
+            );
+        }
+        for modulo in [BigInt::from(8), BigInt::from(7)] {
+            assert_eq!(
+                exponenciacao_modular(&base, &BigInt::from(-1), &modulo),
+                Err(RsaError::InvalidKey)
+            );
+        }
+        assert_eq!(
+            exponenciacao_modular(&base, &exp, &BigInt::from(7)),
+            Ok(BigInt::from(6))
+        );
+        assert_eq!(
+            exponenciacao_modular(&base, &exp, &BigInt::from(8)),
+            Ok(BigInt::from(5))
+        );
+    }
+
+    #[test]
+    fn inverso_modular_rejeita_elementos_sem_inverso() {
+        assert_eq!(
+            inverso_modular(&BigInt::from(3), &BigInt::from(7)),
+            Ok(BigInt::from(5))
+        );
+        assert_eq!(
+            inverso_modular(&BigInt::from(-3), &BigInt::from(7)),
+            Ok(BigInt::from(2))
+        );
+        for (e, phi_n) in [(6, 9), (5, 1), (5, 0), (5, -7)] {
+            assert_eq!(
+                inverso_modular(&BigInt::from(e), &BigInt::from(phi_n)),
+                Err(RsaError::NotInvertible),
+                "{} mod {}",
+                e,
+                phi_n
+            );
+        }
+    }
+
+    /// Módulos ímpares de uma a quatro palavras de 64 bits, incluindo os
+    /// extremos de cada tamanho, e bases com sinal sorteadas abaixo e acima deles.
+    fn casos_de_exponenciacao() -> Vec<(BigInt, BigInt, BigInt)> {
+        let mut rng = StdRng::seed_from_u64(13);
+        let mut casos = Vec::new();
+        for palavras in 1..=4u64 {
+            let bits = 64 * palavras;
+            let mut modulos: Vec<BigInt> = vec![
+                (BigInt::one() << bits) - 1,
+                (BigInt::one() << (bits - 1)) + 1,
+            ];
+            for _ in 0..4 {
+                let tamanho = rng.gen_range(bits - 63..=bits);
+                modulos.push(BigInt::from(rng.gen_biguint(tamanho)) | BigInt::one());
+            }
+            for modulo in modulos {
+                let bits_base = modulo.bits() + 8;
+                let bits_exp = rng.gen_range(1..=bits + 64);
+                let base = rng.gen_bigint(bits_base);
+                let exp = BigInt::from(rng.gen_biguint(bits_exp));
+                casos.push((base.clone(), BigInt::zero(), modulo.clone()));
+                casos.push((base, exp, modulo));
+            }
+        }
+        casos
+    }
+
+    #[test]
+    fn multiplicacao_de_montgomery_concorda_com_o_produto_reduzido() {
+        let mut rng = StdRng::seed_from_u64(19);
+        for (_, _, modulo) in casos_de_exponenciacao() {
+            let contexto = ContextoMontgomery::new(&modulo).unwrap();
+            for _ in 0..8 {
+                let a = rng.gen_bigint_range(&BigInt::zero(), &modulo);
+                let b = rng.gen_bigint_range(&BigInt::zero(), &modulo);
+                let produto = contexto
+                    .multiplicar(&contexto.para_montgomery(&a), &contexto.para_montgomery(&b));
+                assert_eq!(produto, contexto.para_montgomery(&(&a * &b)));
+                assert_eq!(contexto.de_montgomery(&produto), (&a * &b) % &modulo);
+            }
+            let maximo = &modulo - 1;
+            let produto = contexto.multiplicar(
+                &contexto.para_montgomery(&maximo),
+                &contexto.para_montgomery(&maximo),
+            );
+            assert_eq!(contexto.de_montgomery(&produto), BigInt::one());
+        }
+    }
+
+    #[test]
+    fn todos_os_metodos_concordam_com_a_referencia() {
+        let mut metodos = vec![MetodoExponenciacao::Binario];
+        for k in [0, 1, 2, 5, 8, 9] {
+            metodos.push(MetodoExponenciacao::JanelaFixa(k));
+            metodos.push(MetodoExponenciacao::JanelaDeslizante(k));
+        }
+        for (base, exp, modulo) in casos_de_exponenciacao() {
+            let esperado = exponenciacao_modular_referencia(&base, &exp, &modulo).unwrap();
+            assert_eq!(esperado, base.mod_floor(&modulo).modpow(&exp, &modulo));
+            for metodo in &metodos {
+                assert_eq!(
+                    exponenciacao_modular_com_metodo(&base, &exp, &modulo, *metodo),
+                    Ok(esperado.clone()),
+                    "{:?}: {}^{} mod {}",
+                    metodo,
+                    base,
+                    exp,
+                    modulo
+                );
+            }
+            assert_eq!(exponenciacao_modular(&base, &exp, &modulo), Ok(esperado));
+        }
+    }
+
+    #[test]
+    fn resultado_fica_entre_zero_e_o_modulo() {
+        assert_eq!(
+            exponenciacao_modular(&BigInt::from(-3), &BigInt::one(), &BigInt::from(8)),
+            Ok(BigInt::from(5))
+        );
+        assert_eq!(
+            exponenciacao_modular(&BigInt::from(-3), &BigInt::from(3), &BigInt::from(10)),
+            Ok(BigInt::from(3))
+        );
+        assert_eq!(
+            exponenciacao_modular(&BigInt::from(-3), &BigInt::one(), &BigInt::from(7)),
+            Ok(BigInt::from(4))
+        );
+        for exp in [BigInt::zero(), BigInt::from(5)] {
+            assert_eq!(
+                exponenciacao_modular(&BigInt::from(-3), &exp, &BigInt::one()),
+                Ok(BigInt::zero())
+            );
+        }
+    }
+
+    #[test]
+    fn escada_concorda_com_a_referencia() {
+        for (base, exp, modulo) in casos_de_exponenciacao() {
+            assert_eq!(
+                exponenciacao_modular_tempo_constante(&base, &exp, &modulo),
+                exponenciacao_modular_referencia(&base, &exp, &modulo),
+                "{}^{} mod {}",
+                base,
+                exp,
+                modulo
+            );
+        }
+    }
+
+    #[test]
+    fn escada_rejeita_operandos_invalidos() {
+        let (base, exp) = (BigInt::from(3), BigInt::from(2));
+        assert_eq!(
+            exponenciacao_modular_tempo_constante(&base, &BigInt::from(-2), &BigInt::from(7)),
+            Err(RsaError::InvalidKey)
+        );
+        for modulo in [BigInt::zero(), BigInt::from(-7)] {
+            assert_eq!(
+                exponenciacao_modular_tempo_constante(&base, &exp, &modulo),
+                Err(RsaError::InvalidKey)
+            );
+        }
+        for modulo in [BigInt::one(), BigInt::from(8)] {
+            assert_eq!(
+                exponenciacao_modular_tempo_constante(&base, &exp, &modulo),
+                Err(RsaError::NotInvertible)
+            );
+        }
+    }
+}
@@ -0,0 +1,284 @@
+// --------------------------------------------------------
+// Multiplicação de Montgomery
+// --------------------------------------------------------
+
+use crate::error::RsaError;
+use num::Integer;
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::One;
+
+/// Maior janela aceita pelos métodos de janela; janelas maiores são reduzidas
+/// a este valor (a tabela de 2^8 potências já não compensa para RSA).
+pub const TAMANHO_MAXIMO_JANELA: u32 = 8;
+
+/// Contexto de Montgomery para um módulo ímpar n.
+///
+/// Com R = 2^(64·s), onde s é o número de palavras de 64 bits de n, guarda
+/// R² mod n e n' = −n⁻¹ mod 2^64. Cada produto é reduzido pela REDC
+/// (variante CIOS), que troca a divisão por n por multiplicações e
+/// deslocamentos de palavras.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextoMontgomery {
+    modulo: BigInt,
+    palavras: Vec<u64>,
+    r2: Vec<u64>,
+    n_linha: u64,
+}
+
+impl ContextoMontgomery {
+    /// Pré-calcula R² mod n e n' para o módulo `n`.
+    ///
+    /// Retorna `RsaError::NotInvertible` se `n` não for ímpar e maior que 1,
+    /// pois nesse caso n não é invertível módulo R.
+    pub fn new(n: &BigInt) -> Result<Self, RsaError> {
+        if *n <= BigInt::one() || n.is_even() {
+            return Err(RsaError::NotInvertible);
+        }
+
+        let palavras = n.magnitude().to_u64_digits();
+        let s = palavras.len();
+        let r2 = (BigInt::one() << (128 * s)) % n;
+
+        Ok(ContextoMontgomery {
+            modulo: n.clone(),
+            r2: completar(r2.magnitude().to_u64_digits(), s),
+            n_linha: inverso_negativo_palavra(palavras[0]),
+            palavras,
+        })
+    }
+
+    /// Módulo n deste contexto.
+    pub fn modulo(&self) -> &BigInt {
+        &self.modulo
+    }
+
+    /// base^exp mod n, com square-and-multiply sobre a forma de Montgomery.
+    ///
+    /// Espera `exp >= 0`; expoentes negativos são tratados como zero.
+    pub fn exponenciar(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        let base = self.para_montgomery(base);
+        let mut resultado = self.um();
+
+        if exp.sign() == Sign::Plus {
+            for i in (0..exp.bits()).rev() {
+                resultado = self.multiplicar(&resultado, &resultado);
+                if exp.bit(i) {
+                    resultado = self.multiplicar(&resultado, &base);
+                }
+            }
+        }
+        self.de_montgomery(&resultado)
+    }
+
+    /// base^exp mod n pelo método *k-ário* de janela fixa.
+    ///
+    /// Pré-calcula base^0, ..., base^(2^k − 1) e consome o expoente em dígitos
+    /// de `k` bits, do mais significativo para o menos: k quadrados seguidos de
+    /// no máximo um produto por dígito.
+    pub fn exponenciar_janela_fixa(&self, base: &BigInt, exp: &BigInt, k: u32) -> BigInt {
+        let k = limitar_janela(k);
+        let base = self.para_montgomery(base);
+
+        let mut tabela = vec![self.um()];
+        for i in 1..1usize << k {
+            tabela.push(self.multiplicar(&tabela[i - 1], &base));
+        }
+
+        let mut resultado = self.um();
+        if exp.sign() == Sign::Plus {
+            let digitos = exp.bits().div_ceil(u64::from(k));
+            for posicao in (0..digitos).rev() {
+                for _ in 0..k {
+                    resultado = self.multiplicar(&resultado, &resultado);
+                }
+                let digito = ler_bits(exp, posicao * u64::from(k), k);
+                if digito != 0 {
+                    resultado = self.multiplicar(&resultado, &tabela[digito]);
+                }
+            }
+        }
+        self.de_montgomery(&resultado)
+    }
+
+    /// base^exp mod n pelo método de janela deslizante.
+    ///
+    /// Pré-calcula só as potências ímpares base^1, base^3, ..., base^(2^k − 1);
+    /// cada janela começa e termina em um bit 1, e as sequências de zeros entre
+    /// janelas custam apenas quadrados.
+    pub fn exponenciar_janela_deslizante(&self, base: &BigInt, exp: &BigInt, k: u32) -> BigInt {
+        let k = limitar_janela(k);
+        let base = self.para_montgomery(base);
+
+        let quadrado = self.multiplicar(&base, &base);
+        let mut impares = vec![base];
+        for i in 1..1usize << (k - 1) {
+            impares.push(self.multiplicar(&impares[i - 1], &quadrado));
+        }
+
+        let mut resultado = self.um();
+        if exp.sign() == Sign::Plus {
+            let mut i = exp.bits();
+            while i > 0 {
+                if !exp.bit(i - 1) {
+                    resultado = self.multiplicar(&resultado, &resultado);
+                    i -= 1;
+                    continue;
+                }
+
+                // Janela [fim, i) com no máximo k bits, terminada em um bit 1
+                let mut fim = i.saturating_sub(u64::from(k));
+                while !exp.bit(fim) {
+                    fim += 1;
+                }
+                let largura = (i - fim) as u32;
+                for _ in 0..largura {
+                    resultado = self.multiplicar(&resultado, &resultado);
+                }
+                let valor = ler_bits(exp, fim, largura);
+                resultado = self.multiplicar(&resultado, &impares[valor >> 1]);
+                i = fim;
+            }
+        }
+        self.de_montgomery(&resultado)
+    }
+
+    /// x·R mod n, a representação de Montgomery de `x`.
+    pub(crate) fn para_montgomery(&self, x: &BigInt) -> Vec<u64> {
+        let x = x.mod_floor(&self.modulo);
+        let x = completar(x.magnitude().to_u64_digits(), self.palavras.len());
+        self.multiplicar(&x, &self.r2)
+    }
+
+    /// Inverso de `para_montgomery`: x·R⁻¹ mod n.
+    pub(crate) fn de_montgomery(&self, x: &[u64]) -> BigInt {
+        let mut um = vec![0u64; self.palavras.len()];
+        um[0] = 1;
+        let resultado = self.multiplicar(x, &um);
+        BigInt::from_biguint(Sign::Plus, BigUint::new(para_u32(&resultado)))
+    }
+
+    /// R mod n, a representação de Montgomery de 1.
+    pub(crate) fn um(&self) -> Vec<u64> {
+        self.para_montgomery(&BigInt::one())
+    }
+
+    /// Produto de Montgomery a·b·R⁻¹ mod n (CIOS), para a, b < n.
+    pub(crate) fn multiplicar(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let n = &self.palavras;
+        let s = n.len();
+        let mut t = vec![0u64; s + 2];
+
+        for &bi in b {
+            // t += a * b[i]
+            let mut vai_um = 0u64;
+            for j in 0..s {
+                let soma = t[j] as u128 + a[j] as u128 * bi as u128 + vai_um as u128;
+                t[j] = soma as u64;
+                vai_um = (soma >> 64) as u64;
+            }
+            let soma = t[s] as u128 + vai_um as u128;
+            t[s] = soma as u64;
+            t[s + 1] = (soma >> 64) as u64;
+
+            // t = (t + m * n) / 2^64, com m escolhido para zerar a palavra baixa
+            let m = t[0].wrapping_mul(self.n_linha);
+            let soma = t[0] as u128 + m as u128 * n[0] as u128;
+            let mut vai_um = (soma >> 64) as u64;
+            for j in 1..s {
+                let soma = t[j] as u128 + m as u128 * n[j] as u128 + vai_um as u128;
+                t[j - 1] = soma as u64;
+                vai_um = (soma >> 64) as u64;
+            }
+            let soma = t[s] as u128 + vai_um as u128;
+            t[s - 1] = soma as u64;
+            t[s] = t[s + 1] + (soma >> 64) as u64;
+        }
+
+        // O resultado fica em [0, 2n): subtrai n e escolhe, sem desvios, entre
+        // t e t − n conforme o empréstimo final
+        let mut diferenca = vec![0u64; s];
+        let mut empresta = 0u64;
+        for j in 0..s {
+            let (parcial, e1) = t[j].overflowing_sub(n[j]);
+            let (parcial, e2) = parcial.overflowing_sub(empresta);
+            diferenca[j] = parcial;
+            empresta = (e1 | e2) as u64;
+        }
+        let manter = empresta & !t[s] & 1;
+        trocar_condicional(manter, &mut t[..s], &mut diferenca);
+        diferenca
+    }
+
+    /// base^exp mod n pela escada de Montgomery, em tempo constante no expoente.
+    ///
+    /// Percorre sempre max(bits(n), bits(exp)) bits e, em cada um, faz
+    /// exatamente um produto e um quadrado; o bit só decide uma troca
+    /// condicional feita com máscaras. Como `exp` é um expoente privado menor
+    /// que n, nem o número de iterações nem a sequência de operações dependem
+    /// do seu valor.
+    ///
+    /// Espera `exp >= 0`; o sinal é ignorado, então quem recebe expoentes de
+    /// fora deve validá-los antes, como faz `exponenciacao_modular_tempo_constante`.
+    pub fn exponenciar_tempo_constante(&self, base: &BigInt, exp: &BigInt) -> BigInt {
+        let total = self.modulo.bits().max(exp.bits());
+        let mut digitos = exp.magnitude().to_u64_digits();
+        digitos.resize(total.div_ceil(64) as usize, 0);
+
+        // Invariante: r1 = r0 · base
+        let mut r0 = self.um();
+        let mut r1 = self.para_montgomery(base);
+        for i in (0..total).rev() {
+            let bit = (digitos[(i / 64) as usize] >> (i % 64)) & 1;
+            trocar_condicional(bit, &mut r0, &mut r1);
+            r1 = self.multiplicar(&r0, &r1);
+            r0 = self.multiplicar(&r0, &r0);
+            trocar_condicional(bit, &mut r0, &mut r1);
+        }
+        self.de_montgomery(&r0)
+    }
+}
+
+/// Troca `a` e `b` quando `bit` é 1, sem desvios que dependam de `bit`.
+fn trocar_condicional(bit: u64, a: &mut [u64], b: &mut [u64]) {
+    let mascara = bit.wrapping_neg();
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let t = mascara & (*x ^ *y);
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+/// Restringe o tamanho da janela a [1, TAMANHO_MAXIMO_JANELA].
+fn limitar_janela(k: u32) -> u32 {
+    k.clamp(1, TAMANHO_MAXIMO_JANELA)
+}
+
+/// Os `largura` bits de `exp` a partir da posição `inicio`.
+fn ler_bits(exp: &BigInt, inicio: u64, largura: u32) -> usize {
+    (0..u64::from(largura))
+        .rev()
+        .fold(0, |valor, i| valor << 1 | usize::from(exp.bit(inicio + i)))
+}
+
+/// −x⁻¹ mod 2^64 para `x` ímpar, pela iteração de Newton (cada passo dobra
+/// os bits corretos).
+fn inverso_negativo_palavra(x: u64) -> u64 {
+    let mut inverso = 1u64;
+    for _ in 0..6 {
+        inverso = inverso.wrapping_mul(2u64.wrapping_sub(x.wrapping_mul(inverso)));
+    }
+    inverso.wrapping_neg()
+}
+
+/// Completa com zeros à direita (palavras mais significativas) até `s` palavras.
+fn completar(mut palavras: Vec<u64>, s: usize) -> Vec<u64> {
+    palavras.resize(s, 0);
+    palavras
+}
+
+fn para_u32(palavras: &[u64]) -> Vec<u32> {
+    palavras
+        .iter()
+        .flat_map(|&p| [p as u32, (p >> 32) as u32])
+        .collect()
+}
@@ -0,0 +1,183 @@
+// --------------------------------------------------------
+// Cegamento (Blinding) das Operações Privadas
+// --------------------------------------------------------
+
+use crate::arithmetic::{exponenciacao_modular, inverso_modular};
+use crate::error::RsaError;
+use crate::random::RandomSource;
+use num_bigint::{BigInt, RandBigInt};
+use std::sync::Mutex;
+
+/// Quantas operações um mesmo par de cegamento atende antes de ser sorteado
+/// de novo (o mesmo limite do OpenSSL).
+pub const LIMITE_USOS_CEGAMENTO: u32 = 32;
+
+/// Par (r^e mod n, r⁻¹ mod n) para um r aleatório invertível módulo n.
+#[derive(Clone)]
+pub(crate) struct ParCegamento {
+    r_e: BigInt,
+    r_inv: BigInt,
+    usos: u32,
+}
+
+impl ParCegamento {
+    /// Sorteia r em [2, n) até encontrar um invertível e calcula o par.
+    pub(crate) fn gerar<R: RandomSource + ?Sized>(
+        n: &BigInt,
+        e: &BigInt,
+        rng: &mut R,
+    ) -> Result<Self, RsaError> {
+        loop {
+            let r = rng.gen_bigint_range(&BigInt::from(2), n);
+            let r_inv = match inverso_modular(&r, n) {
+                Ok(r_inv) => r_inv,
+                Err(RsaError::NotInvertible) => continue,
+                Err(erro) => return Err(erro),
+            };
+            return Ok(ParCegamento {
+                r_e: exponenciacao_modular(&r, e, n)?,
+                r_inv,
+                usos: 0,
+            });
+        }
+    }
+
+    /// Avança para o par de r²: (r^e)² e (r⁻¹)², bem mais barato que sortear um novo r.
+    fn atualizar(&mut self, n: &BigInt) {
+        self.r_e = (&self.r_e * &self.r_e) % n;
+        self.r_inv = (&self.r_inv * &self.r_inv) % n;
+        self.usos += 1;
+    }
+}
+
+/// Par de cegamento guardado junto da chave privada.
+///
+/// Clonar a chave não copia o par (cada cópia sorteia o seu), e o cache não
+/// participa da comparação entre chaves.
+#[derive(Default)]
+pub(crate) struct CacheCegamento(Mutex<Option<ParCegamento>>);
+
+impl CacheCegamento {
+    /// Calcula `operacao(c)` sobre o bloco cegado c · r^e mod n e remove o
+    /// cegamento do resultado multiplicando por r⁻¹.
+    ///
+    /// Como (c · r^e)^d = c^d · r, o expoente privado nunca é aplicado a um
+    /// valor escolhido pelo atacante. O par é atualizado a cada uso e
+    /// sorteado de novo após `LIMITE_USOS_CEGAMENTO` usos.
+    pub(crate) fn aplicar<F>(
+        &self,
+        c: &BigInt,
+        n: &BigInt,
+        e: &BigInt,
+        operacao: F,
+    ) -> Result<BigInt, RsaError>
+    where
+        F: FnOnce(&BigInt) -> Result<BigInt, RsaError>,
+    {
+        self.aplicar_com_rng(c, n, e, &mut rand::thread_rng(), operacao)
+    }
+
+    /// Variante de `aplicar` que sorteia um novo r a partir de `rng`.
+    pub(crate) fn aplicar_com_rng<R, F>(
+        &self,
+        c: &BigInt,
+        n: &BigInt,
+        e: &BigInt,
+        rng: &mut R,
+        operacao: F,
+    ) -> Result<BigInt, RsaError>
+    where
+        R: RandomSource + ?Sized,
+        F: FnOnce(&BigInt) -> Result<BigInt, RsaError>,
+    {
+        let par = {
+            let mut cache = self.0.lock().unwrap_or_else(|erro| erro.into_inner());
+            let par = match cache.take() {
+                Some(par) if par.usos < LIMITE_USOS_CEGAMENTO => par,
+                _ => ParCegamento::gerar(n, e, rng)?,
+            };
+            let mut proximo = par.clone();
+            proximo.atualizar(n);
+            *cache = Some(proximo);
+            par
+        };
+
+        let cegado = (c * &par.r_e) % n;
+        let resultado = operacao(&cegado)?;
+        Ok((resultado * &par.r_inv) % n)
+    }
+}
+
+impl Clone for CacheCegamento {
+    fn clone(&self) -> Self {
+        CacheCegamento::default()
+    }
+}
+
+impl PartialEq for CacheCegamento {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for CacheCegamento {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::One;
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+
+    /// (n, e, d) com p = 2^89 − 1 e q = 2^61 − 1.
+    fn chave() -> (BigInt, BigInt, BigInt) {
+        let p: BigInt = (BigInt::one() << 89) - 1;
+        let q: BigInt = (BigInt::one() << 61) - 1;
+        let e = BigInt::from(65537);
+        let phi = (&p - 1) * (&q - 1);
+        let d = inverso_modular(&e, &phi).unwrap();
+        (p * q, e, d)
+    }
+
+    /// Aplica o cache a `c` e informa se um novo r foi sorteado de `rng`.
+    fn aplicar_e_observar(
+        cache: &CacheCegamento,
+        c: &BigInt,
+        (n, e, d): &(BigInt, BigInt, BigInt),
+        rng: &mut StdRng,
+    ) -> bool {
+        let mut antes = rng.clone();
+        let m = cache
+            .aplicar_com_rng(c, n, e, rng, |x| exponenciacao_modular(x, d, n))
+            .unwrap();
+        assert_eq!(m, exponenciacao_modular(c, d, n).unwrap());
+        antes.next_u64() != rng.clone().next_u64()
+    }
+
+    #[test]
+    fn par_e_sorteado_de_novo_apos_o_limite_de_usos() {
+        let chave = chave();
+        let mut rng = StdRng::seed_from_u64(16);
+        let cache = CacheCegamento::default();
+        let usos = 3 * LIMITE_USOS_CEGAMENTO + 1;
+        for uso in 0..usos {
+            let c = BigInt::from(uso) * 1_000_003 + 2;
+            let sorteou = aplicar_e_observar(&cache, &c, &chave, &mut rng);
+            assert_eq!(sorteou, uso % LIMITE_USOS_CEGAMENTO == 0, "uso {}", uso);
+        }
+    }
+
+    #[test]
+    fn clone_comeca_sem_par() {
+        let chave = chave();
+        let mut rng = StdRng::seed_from_u64(17);
+        let cache = CacheCegamento::default();
+        let c = BigInt::from(12345);
+        assert!(aplicar_e_observar(&cache, &c, &chave, &mut rng));
+
+        let copia = cache.clone();
+        assert!(aplicar_e_observar(&copia, &c, &chave, &mut rng));
+        assert!(!aplicar_e_observar(&cache, &c, &chave, &mut rng));
+        assert!(!aplicar_e_observar(&copia, &c, &chave, &mut rng));
+    }
+}
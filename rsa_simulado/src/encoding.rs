@@ -0,0 +1,224 @@
+// --------------------------------------------------------
+// Funções de Conversão Mensagem ↔ Números
+// --------------------------------------------------------
+
+use crate::error::RsaError;
+use num_bigint::{BigInt, Sign};
+
+/// Tamanho, em bytes, do prefixo que guarda o comprimento da mensagem.
+const TAMANHO_PREFIXO: usize = 4;
+
+/// Quantos bytes cabem em cada bloco para o módulo `n`.
+///
+/// Um bloco de `k − 1` bytes, onde `k` é o tamanho de n em bytes, é sempre
+/// menor que n. Retorna `RsaError::InvalidKey` se n não comporta nem um byte.
+pub fn capacidade_do_bloco(n: &BigInt) -> Result<usize, RsaError> {
+    let k = tamanho_em_bytes(n);
+    if k < 2 {
+        return Err(RsaError::InvalidKey);
+    }
+    Ok(k - 1)
+}
+
+/// Empacota os bytes em blocos big-endian do maior tamanho que cabe abaixo de `n`.
+///
+/// A mensagem é precedida pelo seu comprimento (4 bytes, big-endian) e o último
+/// bloco é completado com zeros, de modo que `blocos_para_bytes` consiga
+/// recuperar exatamente os bytes originais.
+pub fn bytes_para_blocos(mensagem: &[u8], n: &BigInt) -> Result<Vec<BigInt>, RsaError> {
+    let capacidade = capacidade_do_bloco(n)?;
+    let comprimento = u32::try_from(mensagem.len()).map_err(|_| RsaError::MessageTooLong)?;
+
+    let mut dados = Vec::with_capacity(TAMANHO_PREFIXO + mensagem.len());
+    dados.extend_from_slice(&comprimento.to_be_bytes());
+    dados.extend_from_slice(mensagem);
+
+    Ok(dados
+        .chunks(capacidade)
+        .map(|pedaco| {
+            let mut bloco = pedaco.to_vec();
+            bloco.resize(capacidade, 0);
+            BigInt::from_bytes_be(Sign::Plus, &bloco)
+        })
+        .collect())
+}
+
+/// Inverso de `bytes_para_blocos`.
+///
+/// Retorna `RsaError::InvalidBlock` se algum bloco não couber na capacidade do
+/// módulo e `RsaError::DecodingError` se o prefixo de comprimento for inconsistente.
+pub fn blocos_para_bytes(blocos: &[BigInt], n: &BigInt) -> Result<Vec<u8>, RsaError> {
+    let capacidade = capacidade_do_bloco(n)?;
+
+    let mut dados = Vec::with_capacity(blocos.len() * capacidade);
+    for bloco in blocos {
+        let (sinal, bytes) = bloco.to_bytes_be();
+        if sinal == Sign::Minus || bytes.len() > capacidade {
+            return Err(RsaError::InvalidBlock);
+        }
+        // Restaura os zeros à esquerda perdidos na conversão para BigInt
+        dados.resize(dados.len() + capacidade - bytes.len(), 0);
+        dados.extend_from_slice(&bytes);
+    }
+
+    if dados.len() < TAMANHO_PREFIXO {
+        return Err(RsaError::DecodingError);
+    }
+    let (prefixo, resto) = dados.split_at(TAMANHO_PREFIXO);
+    let mut comprimento = [0u8; TAMANHO_PREFIXO];
+    comprimento.copy_from_slice(prefixo);
+    let comprimento = u32::from_be_bytes(comprimento) as usize;
+
+    // Só o último bloco pode conter preenchimento
+    if comprimento > resto.len() || resto.len() - comprimento >= capacidade {
+        return Err(RsaError::DecodingError);
+    }
+    Ok(resto[..comprimento].to_vec())
+}
+
+/// Converte o texto em blocos numéricos menores que `n`.
+pub fn string_para_numeros(texto: &str, n: &BigInt) -> Result<Vec<BigInt>, RsaError> {
+    bytes_para_blocos(texto.as_bytes(), n)
+}
+
+/// Converte os blocos numéricos de volta em texto.
+///
+/// Retorna `RsaError::DecodingError` se os bytes não formarem UTF-8 válido.
+pub fn numeros_para_string(numeros: &[BigInt], n: &BigInt) -> Result<String, RsaError> {
+    let bytes = blocos_para_bytes(numeros, n)?;
+    String::from_utf8(bytes).map_err(|_| RsaError::DecodingError)
+}
+
+/// Tamanho de `n` em bytes (o `k` da RFC 8017).
+pub fn tamanho_em_bytes(n: &BigInt) -> usize {
+    n.bits().div_ceil(8) as usize
+}
+
+/// I2OSP (RFC 8017, seção 4.1): representa `x` com exatamente `tamanho` bytes big-endian.
+///
+/// Retorna `RsaError::InvalidBlock` se `x` for negativo ou não couber em `tamanho` bytes.
+pub fn inteiro_para_octetos(x: &BigInt, tamanho: usize) -> Result<Vec<u8>, RsaError> {
+    let (sinal, bytes) = x.to_bytes_be();
+    if sinal == Sign::Minus {
+        return Err(RsaError::InvalidBlock);
+    }
+    // `to_bytes_be` devolve [0] para o zero
+    let bytes = if sinal == Sign::NoSign {
+        Vec::new()
+    } else {
+        bytes
+    };
+    if bytes.len() > tamanho {
+        return Err(RsaError::InvalidBlock);
+    }
+
+    let mut octetos = vec![0u8; tamanho - bytes.len()];
+    octetos.extend_from_slice(&bytes);
+    Ok(octetos)
+}
+
+/// OS2IP (RFC 8017, seção 4.2): interpreta os bytes como inteiro big-endian.
+pub fn octetos_para_inteiro(octetos: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, octetos)
+}
+
+/// Converte texto hexadecimal (espaços ignorados) em bytes, para os vetores
+/// de teste.
+#[cfg(test)]
+pub(crate) fn hex_para_octetos(texto: &str) -> Vec<u8> {
+    let digitos: Vec<u8> = texto
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    digitos
+        .chunks(2)
+        .map(|par| u8::from_str_radix(std::str::from_utf8(par).unwrap(), 16).unwrap())
+        .collect()
+}
+
+/// Monta a chave privada com os primos `p` e `q`, dados em hexadecimal, e o
+/// expoente público `e`, para os vetores de teste.
+#[cfg(test)]
+pub(crate) fn chave_de_primos_hex(p: &str, q: &str, e: u32) -> crate::keys::RsaPrivateKey {
+    let p = BigInt::parse_bytes(p.as_bytes(), 16).unwrap();
+    let q = BigInt::parse_bytes(q.as_bytes(), 16).unwrap();
+    crate::keys::RsaPrivateKey::a_partir_dos_primos(p, q, BigInt::from(e)).unwrap()
+}
+
+/// Chave de 1024 bits com e = 65537, compartilhada pelos testes dos
+/// preenchimentos e das assinaturas.
+#[cfg(test)]
+pub(crate) fn chave_de_teste_1024() -> crate::keys::RsaPrivateKey {
+    chave_de_primos_hex(
+        "d4297960e7e97cf7de587e533318c87d80eee250faa65308c6189ade5a6bf625\
+         c7d01c0e2b218e7a213c47eb4fe7c5185a6cc8ea750729876fabbba314f82d69",
+        "ec7e02106ffed93224032fb8b76d7bc8469b8a03e60034c3e420d0ce431d1bf8\
+         84e7e74c1fa68dec980225cf7c07644a8cc50a20e938cf46686eedfcd63dbb31",
+        65537,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::One;
+
+    fn modulos() -> Vec<BigInt> {
+        vec![
+            BigInt::from(0x1234),
+            (BigInt::one() << 61) - 1,
+            (BigInt::one() << 521) - 1,
+        ]
+    }
+
+    #[test]
+    fn blocos_preservam_mensagens_de_todos_os_tamanhos() {
+        for n in modulos() {
+            let capacidade = capacidade_do_bloco(&n).unwrap();
+            for tamanho in 0..2 * capacidade + TAMANHO_PREFIXO {
+                let mensagem: Vec<u8> = (0..tamanho).map(|i| (i * 37 + 1) as u8).collect();
+                let blocos = bytes_para_blocos(&mensagem, &n).unwrap();
+                assert!(blocos.iter().all(|bloco| *bloco < n));
+                assert_eq!(
+                    blocos.len(),
+                    (TAMANHO_PREFIXO + tamanho).div_ceil(capacidade)
+                );
+                assert_eq!(blocos_para_bytes(&blocos, &n).unwrap(), mensagem);
+            }
+        }
+    }
+
+    #[test]
+    fn prefixo_de_comprimento_corrompido_e_rejeitado() {
+        for n in modulos() {
+            let capacidade = capacidade_do_bloco(&n).unwrap();
+            let mut blocos = bytes_para_blocos(b"mensagem de teste", &n).unwrap();
+            // Soma 1 ao byte mais significativo do prefixo: comprimento ≥ 2^24
+            blocos[0] += BigInt::one() << (8 * (capacidade - 1));
+            assert_eq!(blocos_para_bytes(&blocos, &n), Err(RsaError::DecodingError));
+
+            // Um bloco inteiro a mais deixa preenchimento demais no fim
+            let mut blocos = bytes_para_blocos(b"abc", &n).unwrap();
+            blocos.push(BigInt::from(0));
+            assert_eq!(blocos_para_bytes(&blocos, &n), Err(RsaError::DecodingError));
+        }
+        assert_eq!(
+            blocos_para_bytes(&[], &modulos()[1]),
+            Err(RsaError::DecodingError)
+        );
+    }
+
+    #[test]
+    fn bloco_grande_demais_ou_negativo_e_rejeitado() {
+        for n in modulos() {
+            let capacidade = capacidade_do_bloco(&n).unwrap();
+            let mut blocos = bytes_para_blocos(b"mensagem de teste", &n).unwrap();
+            let ultimo = blocos.len() - 1;
+            blocos[ultimo] = BigInt::one() << (8 * capacidade);
+            assert_eq!(blocos_para_bytes(&blocos, &n), Err(RsaError::InvalidBlock));
+
+            blocos[ultimo] = BigInt::from(-1);
+            assert_eq!(blocos_para_bytes(&blocos, &n), Err(RsaError::InvalidBlock));
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// --------------------------------------------------------
+// Erros das Operações RSA
+// --------------------------------------------------------
+
+use std::fmt;
+
+/// Falhas possíveis nas operações públicas do crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RsaError {
+    /// O número não tem inverso modular (gcd ≠ 1).
+    NotInvertible,
+    /// O bloco da mensagem é maior ou igual ao módulo n.
+    MessageTooLong,
+    /// O bloco está fora do intervalo válido [0, n).
+    InvalidBlock,
+    /// Os parâmetros não formam uma chave RSA válida.
+    InvalidKey,
+    /// Os blocos descriptografados não formam uma mensagem válida.
+    DecodingError,
+    /// Tamanho em bits insuficiente para a operação pedida.
+    InvalidBitLength(u32),
+    /// A assinatura não confere com a mensagem e a chave pública.
+    InvalidSignature,
+    /// A fonte de aleatoriedade recusou o pedido (entropia insuficiente ou
+    /// ressemeadura exigida).
+    RandomnessError,
+}
+
+impl fmt::Display for RsaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RsaError::NotInvertible => write!(f, "o número não possui inverso modular"),
+            RsaError::MessageTooLong => write!(f, "a mensagem é muito longa para o módulo n"),
+            RsaError::InvalidBlock => write!(f, "bloco fora do intervalo [0, n)"),
+            RsaError::InvalidKey => write!(f, "parâmetros de chave inválidos"),
+            RsaError::DecodingError => write!(f, "falha ao decodificar a mensagem"),
+            RsaError::InvalidBitLength(bits) => write!(f, "tamanho inválido: {} bits", bits),
+            RsaError::InvalidSignature => write!(f, "assinatura inválida"),
+            RsaError::RandomnessError => write!(f, "falha na fonte de aleatoriedade"),
+        }
+    }
+}
+
+impl std::error::Error for RsaError {}
@@ -0,0 +1,377 @@
+// --------------------------------------------------------
+// Geração de Chaves Conforme o FIPS 186-5 (Apêndice A.1.3)
+// --------------------------------------------------------
+
+use crate::arithmetic::inverso_modular;
+use crate::error::RsaError;
+use crate::keys::{
+    funcao_carmichael, gerar_primo_coprimo, validar_expoente, validar_expoente_publico, KeyPair,
+    RsaPrivateKey,
+};
+use crate::primes::especiais::TipoPrimo;
+use crate::primes::{eh_primo, TestePrimalidade, RODADAS_MILLER_RABIN};
+use crate::random::RandomSource;
+use num_bigint::BigInt;
+use num_traits::{One, Signed};
+use std::fmt;
+
+/// Menor módulo aceito pelo FIPS 186-5, em bits.
+pub const TAMANHO_MINIMO_FIPS: u32 = 2048;
+
+/// Diferença mínima entre p e q, em bits abaixo de nlen/2: |p − q| > 2^(nlen/2 − 100).
+const DISTANCIA_MINIMA_PRIMOS: u64 = 100;
+
+/// Rodadas de Miller–Rabin exigidas pela tabela B.1 do FIPS 186-5 para primos
+/// prováveis de `bits_primo` bits (nlen de 2048, 3072 ou 4096 bits).
+///
+/// Retorna `None` para tamanhos fora da tabela.
+pub fn rodadas_miller_rabin_exigidas(bits_primo: u64) -> Option<u32> {
+    match bits_primo {
+        1024 => Some(5),
+        1536 | 2048 => Some(4),
+        _ => None,
+    }
+}
+
+/// Resultado de um requisito do Apêndice A.1.3.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificacaoFips {
+    pub requisito: &'static str,
+    pub detalhe: String,
+    pub aprovada: bool,
+}
+
+/// Relatório de conformidade de uma chave com o FIPS 186-5.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatorioFips {
+    /// Pares (p, q) sorteados até todos os requisitos serem atendidos.
+    pub tentativas: u32,
+    pub verificacoes: Vec<VerificacaoFips>,
+}
+
+impl RelatorioFips {
+    /// Confere uma chave privada já existente contra cada requisito.
+    ///
+    /// Chaves multiprimo são reprovadas: o FIPS 186-5 só admite n = p · q.
+    pub fn auditar(chave: &RsaPrivateKey) -> RelatorioFips {
+        let mut verificacoes = vec![VerificacaoFips {
+            requisito: "quantidade de primos",
+            detalhe: format!("{} fatores primos, exigidos 2", chave.quantidade_primos()),
+            aprovada: chave.quantidade_primos() == 2,
+        }];
+        verificacoes.extend(verificar(chave.p(), chave.q(), chave.e(), Some(chave.d())));
+        RelatorioFips {
+            tentativas: 0,
+            verificacoes,
+        }
+    }
+
+    /// Verdadeiro se todos os requisitos foram atendidos.
+    pub fn aprovado(&self) -> bool {
+        self.verificacoes
+            .iter()
+            .all(|verificacao| verificacao.aprovada)
+    }
+}
+
+impl fmt::Display for RelatorioFips {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for verificacao in &self.verificacoes {
+            let marca = if verificacao.aprovada { "ok" } else { "FALHOU" };
+            writeln!(
+                f,
+                "[{:^6}] {}: {}",
+                marca, verificacao.requisito, verificacao.detalhe
+            )?;
+        }
+        write!(f, "pares sorteados: {}", self.tentativas)
+    }
+}
+
+/// Gera um par de chaves de `bits` bits seguindo o Apêndice A.1.3 (primos
+/// prováveis) e devolve também o relatório das verificações.
+///
+/// Os primos vêm de `gerar_primo` com gcd(e, p − 1) = 1, d vem de
+/// `inverso_modular` módulo λ(n), e o par (p, q) é sorteado de novo até que
+/// todos os requisitos sejam atendidos.
+///
+/// Retorna `RsaError::InvalidBitLength` se `bits` não for 2048, 3072 ou 4096
+/// (os tamanhos da tabela B.1) e `RsaError::InvalidKey` se `e` estiver fora de
+/// 2^16 < e < 2^256 ou for par.
+pub fn gerar_chaves(bits: u32, e: &BigInt) -> Result<(KeyPair, RelatorioFips), RsaError> {
+    gerar_chaves_com_rng(bits, e, &mut rand::thread_rng())
+}
+
+/// Variante de `gerar_chaves` que sorteia os primos a partir de `rng`.
+pub fn gerar_chaves_com_rng<R: RandomSource + ?Sized>(
+    bits: u32,
+    e: &BigInt,
+    rng: &mut R,
+) -> Result<(KeyPair, RelatorioFips), RsaError> {
+    if !bits.is_multiple_of(2) || rodadas_miller_rabin_exigidas(u64::from(bits / 2)).is_none() {
+        return Err(RsaError::InvalidBitLength(bits));
+    }
+    validar_expoente_publico(e, true)?;
+
+    let mut tentativas = 0;
+    loop {
+        tentativas += 1;
+        // O Miller–Rabin exigido pela tabela B.1 é aplicado em `verificar`
+        let teste = TestePrimalidade::default();
+        let p = gerar_primo_coprimo(bits / 2, e, TipoPrimo::Aleatorio, teste, rng)?;
+        let q = gerar_primo_coprimo(bits / 2, e, TipoPrimo::Aleatorio, teste, rng)?;
+
+        let verificacoes = verificar(&p, &q, e, None);
+        if verificacoes.iter().any(|verificacao| !verificacao.aprovada) {
+            continue;
+        }
+
+        let d = inverso_modular(e, &funcao_carmichael(&p, &q))?;
+        let privada = RsaPrivateKey::montar(p, q, e.clone(), d)?;
+        let publica = privada.chave_publica();
+        let relatorio = RelatorioFips {
+            tentativas,
+            verificacoes,
+        };
+        return Ok((KeyPair { publica, privada }, relatorio));
+    }
+}
+
+/// Avalia cada requisito para os primos `p` e `q`; `d`, se omitido, é
+/// calculado módulo λ(n).
+fn verificar(p: &BigInt, q: &BigInt, e: &BigInt, d: Option<&BigInt>) -> Vec<VerificacaoFips> {
+    let nlen = (p * q).bits();
+    let metade = nlen / 2;
+    let lambda = funcao_carmichael(p, q);
+    let mut verificacoes = Vec::new();
+    let mut registrar = |requisito, detalhe: String, aprovada| {
+        verificacoes.push(VerificacaoFips {
+            requisito,
+            detalhe,
+            aprovada,
+        })
+    };
+
+    registrar(
+        "tamanho do módulo",
+        format!("nlen = {} (mínimo {}, par)", nlen, TAMANHO_MINIMO_FIPS),
+        nlen >= u64::from(TAMANHO_MINIMO_FIPS) && nlen.is_multiple_of(2),
+    );
+    registrar(
+        "expoente público",
+        format!("e com {} bits, exigido 2^16 < e < 2^256 e ímpar", e.bits()),
+        validar_expoente_publico(e, true).is_ok(),
+    );
+
+    // p ≥ √2·2^(nlen/2 − 1) ⇔ p² ≥ 2^(nlen − 1), sem aproximar √2
+    let limite_inferior = BigInt::one() << (nlen - 1);
+    let tamanho_ok = |primo: &BigInt| primo.bits() == metade && primo * primo >= limite_inferior;
+    registrar(
+        "limite inferior de p e q",
+        format!("p, q ≥ √2·2^{} com {} bits", metade - 1, metade),
+        tamanho_ok(p) && tamanho_ok(q),
+    );
+
+    let distancia = (p - q).abs();
+    registrar(
+        "distância entre p e q",
+        format!(
+            "|p − q| com {} bits, exigido > 2^{}",
+            distancia.bits(),
+            metade.saturating_sub(DISTANCIA_MINIMA_PRIMOS)
+        ),
+        distancia > BigInt::one() << metade.saturating_sub(DISTANCIA_MINIMA_PRIMOS),
+    );
+
+    registrar(
+        "gcd(e, p − 1) = gcd(e, q − 1) = 1",
+        "e invertível módulo λ(n)".to_string(),
+        validar_expoente(e, p, q).is_ok(),
+    );
+
+    let (detalhe, aprovada) = match rodadas_miller_rabin_exigidas(metade) {
+        Some(exigidas) => {
+            let rodadas = exigidas.max(RODADAS_MILLER_RABIN);
+            (
+                format!("{} rodadas exigidas, {} executadas", exigidas, rodadas),
+                eh_primo(p, rodadas) && eh_primo(q, rodadas),
+            )
+        }
+        None => (
+            format!("primos de {} bits fora da tabela B.1", metade),
+            false,
+        ),
+    };
+    registrar("Miller–Rabin", detalhe, aprovada);
+
+    let d = match d {
+        Some(d) => Ok(d.clone()),
+        None => inverso_modular(e, &lambda),
+    };
+    let (detalhe, aprovada) = match d {
+        Ok(d) => (
+            format!("d com {} bits, exigido 2^{} < d < λ(n)", d.bits(), metade),
+            d > BigInt::one() << metade && d < lambda && (e * &d % &lambda).is_one(),
+        ),
+        Err(_) => ("e não é invertível módulo λ(n)".to_string(), false),
+    };
+    registrar("expoente privado", detalhe, aprovada);
+
+    verificacoes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::chave_de_teste_1024;
+    use crate::hash::Sha256;
+    use crate::keys::EXPOENTE_PUBLICO_PADRAO;
+    use crate::random::HmacDrbg;
+    use rand::SeedableRng;
+
+    // Primos de 1024 bits sorteados por `gerar_chaves_com_rng` a partir do
+    // `HmacDrbg::<Sha256>` com a semente 20, como faz `--semente=20 --fips`
+    const P: &str = "cb5e77b00420b7e056ef8bb3cbe958753dd8dbc6be0940bcb93739ba1e363a81\
+                     b0f9417c377385bbd7a27539eab04d5a49523b3c92dff820669a8ba2ac8bf691\
+                     5427181786825d159f159622948e1272e1d8846729d16b5d022b7b5d279643d0\
+                     c79a6695edb93ca518cc0dc5f5812b4f780b077e4ca8a0a0ff297e2099541677";
+    const Q: &str = "fc83319c277d1336681f1faeb9c447c2e96c2a436a74afba7075d31e53c64484\
+                     d4484f075ef44a90012a2fb632ceaa77c77a9b929b9fe6518041931aea9eb359\
+                     c84f99f1364e48b38e7d2e578ad80ad7a061038c6e6948786082becb9fd2555f\
+                     a11c9f3a7489583d9c195a7bddf79ae660d926c9329fed10b7aedb8b26c5a523";
+
+    fn f4() -> BigInt {
+        BigInt::from(EXPOENTE_PUBLICO_PADRAO)
+    }
+
+    fn primos_fixos() -> (BigInt, BigInt) {
+        let p = BigInt::parse_bytes(P.as_bytes(), 16).unwrap();
+        let q = BigInt::parse_bytes(Q.as_bytes(), 16).unwrap();
+        (p, q)
+    }
+
+    /// Primeiro primo a partir de `inicio`, andando de 2 em 2 no sentido de
+    /// `passo`, com gcd(e, r − 1) = 1.
+    fn primo_a_partir_de(inicio: BigInt, passo: i32) -> BigInt {
+        let mut candidato = inicio;
+        if !candidato.bit(0) {
+            candidato += passo.signum();
+        }
+        while !eh_primo(&candidato, 1) || validar_expoente(&f4(), &candidato, &candidato).is_err() {
+            candidato += 2 * passo.signum();
+        }
+        candidato
+    }
+
+    /// Requisitos reprovados, na ordem do relatório.
+    fn reprovados(verificacoes: &[VerificacaoFips]) -> Vec<&'static str> {
+        verificacoes
+            .iter()
+            .filter(|verificacao| !verificacao.aprovada)
+            .map(|verificacao| verificacao.requisito)
+            .collect()
+    }
+
+    #[test]
+    fn chave_fixa_passa_em_todos_os_requisitos() {
+        let (p, q) = primos_fixos();
+        let chave = RsaPrivateKey::a_partir_dos_primos(p, q, f4()).unwrap();
+        let auditoria = RelatorioFips::auditar(&chave);
+        assert!(auditoria.aprovado(), "{}", auditoria);
+        assert_eq!(auditoria.verificacoes.len(), 8);
+        assert_eq!(auditoria.tentativas, 0);
+    }
+
+    #[test]
+    fn mesma_semente_reproduz_a_chave_fixa() {
+        let gerar = || {
+            let mut rng = HmacDrbg::<Sha256>::seed_from_u64(20);
+            gerar_chaves_com_rng(2048, &f4(), &mut rng).unwrap()
+        };
+        let (chaves, relatorio) = gerar();
+        assert!(relatorio.aprovado(), "{}", relatorio);
+        assert!(relatorio.tentativas >= 1);
+        assert_eq!(chaves.publica.n.bits(), 2048);
+        assert_eq!(
+            (chaves.privada.p().clone(), chaves.privada.q().clone()),
+            primos_fixos()
+        );
+
+        let (de_novo, _) = gerar();
+        assert_eq!(de_novo.privada, chaves.privada);
+        assert_eq!(de_novo.publica, chaves.publica);
+    }
+
+    #[test]
+    fn primos_proximos_demais_sao_reprovados() {
+        let (p, _) = primos_fixos();
+        // |p − q| ≤ 2^(1024 − 100): q é o primeiro primo depois de p + 2^924
+        let q = primo_a_partir_de(&p + (BigInt::one() << 923), 1);
+        assert!((&q - &p).bits() <= 924);
+        assert_eq!(
+            reprovados(&verificar(&p, &q, &f4(), None)),
+            ["distância entre p e q"]
+        );
+    }
+
+    #[test]
+    fn primo_abaixo_de_raiz_de_2_vezes_2_elevado_a_nlen_2_menos_1_e_reprovado() {
+        // p com 1024 bits, mas p < √2·2^1023; q perto de 2^1024 mantém nlen = 2048
+        let p = primo_a_partir_de((BigInt::one() << 1023) + 1, 1);
+        let q = primo_a_partir_de((BigInt::one() << 1024) - 1, -1);
+        assert_eq!((&p * &q).bits(), 2048);
+        assert_eq!(
+            reprovados(&verificar(&p, &q, &f4(), None)),
+            ["limite inferior de p e q"]
+        );
+    }
+
+    #[test]
+    fn expoente_privado_ate_2_elevado_a_nlen_2_e_reprovado() {
+        let (p, q) = primos_fixos();
+        let d: BigInt = BigInt::one() << 1024;
+        assert_eq!(
+            reprovados(&verificar(&p, &q, &f4(), Some(&d))),
+            ["expoente privado"]
+        );
+    }
+
+    #[test]
+    fn tamanho_fora_da_tabela_e_reprovado() {
+        for bits in [1024, 2047, 2049, 2050, 8192] {
+            assert_eq!(
+                gerar_chaves(bits, &f4()).map(|_| ()),
+                Err(RsaError::InvalidBitLength(bits))
+            );
+        }
+        assert_eq!(
+            gerar_chaves(2048, &BigInt::from(3)).map(|_| ()),
+            Err(RsaError::InvalidKey)
+        );
+
+        let auditoria = RelatorioFips::auditar(&chave_de_teste_1024());
+        assert_eq!(
+            reprovados(&auditoria.verificacoes),
+            ["tamanho do módulo", "Miller–Rabin"]
+        );
+    }
+
+    #[test]
+    fn rodadas_exigidas_por_tamanho_de_primo() {
+        for (bits_primo, rodadas) in [
+            (1024, Some(5)),
+            (1536, Some(4)),
+            (2048, Some(4)),
+            (512, None),
+            (1025, None),
+            (4096, None),
+        ] {
+            assert_eq!(
+                rodadas_miller_rabin_exigidas(bits_primo),
+                rodadas,
+                "{}",
+                bits_primo
+            );
+        }
+    }
+}
@@ -0,0 +1,727 @@
+// --------------------------------------------------------
+// Funções de Hash (SHA-1, SHA-2), HMAC e MGF1
+// --------------------------------------------------------
+
+/// Interface de streaming comum às funções de hash do crate.
+pub trait FuncaoHash: Clone {
+    /// Tamanho do resumo, em bytes.
+    const TAMANHO_SAIDA: usize;
+    /// Tamanho do bloco interno, em bytes (usado pelo HMAC).
+    const TAMANHO_BLOCO: usize;
+
+    fn new() -> Self;
+
+    /// Acrescenta `dados` à mensagem sendo resumida.
+    fn update(&mut self, dados: &[u8]);
+
+    /// Conclui o cálculo e devolve o resumo.
+    fn finalize(self) -> Vec<u8>;
+
+    /// Resume `dados` de uma só vez.
+    fn digest(dados: &[u8]) -> Vec<u8> {
+        let mut hash = Self::new();
+        hash.update(dados);
+        hash.finalize()
+    }
+}
+
+/// Acumula os bytes recebidos em blocos completos e aplica o preenchimento
+/// Merkle–Damgård comum à família SHA: 0x80, zeros e o comprimento em bits.
+#[derive(Clone)]
+struct Acumulador<const BLOCO: usize> {
+    buffer: Vec<u8>,
+    comprimento: u128,
+}
+
+impl<const BLOCO: usize> Acumulador<BLOCO> {
+    fn new() -> Self {
+        Acumulador {
+            buffer: Vec::with_capacity(BLOCO),
+            comprimento: 0,
+        }
+    }
+
+    fn alimentar(&mut self, mut dados: &[u8], mut comprimir: impl FnMut(&[u8])) {
+        self.comprimento = self.comprimento.wrapping_add(dados.len() as u128);
+
+        while !dados.is_empty() {
+            let faltam = BLOCO - self.buffer.len();
+            let (inicio, resto) = dados.split_at(faltam.min(dados.len()));
+            self.buffer.extend_from_slice(inicio);
+            dados = resto;
+
+            if self.buffer.len() == BLOCO {
+                comprimir(&self.buffer);
+                self.buffer.clear();
+            }
+        }
+    }
+
+    /// Completa o último bloco; o comprimento ocupa `bytes_comprimento` bytes
+    /// big-endian (8 para blocos de 64 bytes, 16 para blocos de 128 bytes).
+    fn preencher(mut self, bytes_comprimento: usize, mut comprimir: impl FnMut(&[u8])) {
+        let bits = self.comprimento.wrapping_mul(8).to_be_bytes();
+
+        self.buffer.push(0x80);
+        if self.buffer.len() > BLOCO - bytes_comprimento {
+            self.buffer.resize(BLOCO, 0);
+            comprimir(&self.buffer);
+            self.buffer.clear();
+        }
+        self.buffer.resize(BLOCO - bytes_comprimento, 0);
+        self.buffer
+            .extend_from_slice(&bits[bits.len() - bytes_comprimento..]);
+        comprimir(&self.buffer);
+    }
+}
+
+/// Valor inicial do SHA-1 (FIPS 180-4, seção 5.3.1).
+const H1: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// SHA-1 (FIPS 180-4). Não é mais resistente a colisões; existe apenas para
+/// interoperar com OAEP e MGF1 no seu parâmetro padrão.
+#[derive(Clone)]
+pub struct Sha1 {
+    estado: [u32; 5],
+    acumulador: Acumulador<64>,
+}
+
+fn comprimir_sha1(estado: &mut [u32; 5], bloco: &[u8]) {
+    let mut w = [0u32; 80];
+    for (t, palavra) in bloco.chunks_exact(4).enumerate() {
+        w[t] = u32::from_be_bytes([palavra[0], palavra[1], palavra[2], palavra[3]]);
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *estado;
+    for (t, &palavra) in w.iter().enumerate() {
+        let (f, k) = match t {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(palavra);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (valor, novo) in estado.iter_mut().zip([a, b, c, d, e]) {
+        *valor = valor.wrapping_add(novo);
+    }
+}
+
+impl FuncaoHash for Sha1 {
+    const TAMANHO_SAIDA: usize = 20;
+    const TAMANHO_BLOCO: usize = 64;
+
+    fn new() -> Self {
+        Sha1 {
+            estado: H1,
+            acumulador: Acumulador::new(),
+        }
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        let estado = &mut self.estado;
+        self.acumulador
+            .alimentar(dados, |bloco| comprimir_sha1(estado, bloco));
+    }
+
+    fn finalize(mut self) -> Vec<u8> {
+        let estado = &mut self.estado;
+        self.acumulador
+            .preencher(8, |bloco| comprimir_sha1(estado, bloco));
+        self.estado.iter().flat_map(|v| v.to_be_bytes()).collect()
+    }
+}
+
+/// Constantes de rodada do SHA-256 (FIPS 180-4, seção 4.2.2).
+const K256: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Valor inicial do SHA-256 (FIPS 180-4, seção 5.3.3).
+const H256: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Valor inicial do SHA-224 (FIPS 180-4, seção 5.3.2).
+const H224: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
+
+/// Núcleo comum ao SHA-256 e ao SHA-224, que diferem apenas no valor
+/// inicial e no truncamento da saída.
+#[derive(Clone)]
+struct NucleoSha256 {
+    estado: [u32; 8],
+    acumulador: Acumulador<64>,
+}
+
+impl NucleoSha256 {
+    fn new(valor_inicial: [u32; 8]) -> Self {
+        NucleoSha256 {
+            estado: valor_inicial,
+            acumulador: Acumulador::new(),
+        }
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        let estado = &mut self.estado;
+        self.acumulador
+            .alimentar(dados, |bloco| comprimir_sha256(estado, bloco));
+    }
+
+    fn finalize(mut self, tamanho_saida: usize) -> Vec<u8> {
+        let estado = &mut self.estado;
+        self.acumulador
+            .preencher(8, |bloco| comprimir_sha256(estado, bloco));
+        let mut resumo: Vec<u8> = self.estado.iter().flat_map(|v| v.to_be_bytes()).collect();
+        resumo.truncate(tamanho_saida);
+        resumo
+    }
+}
+
+fn comprimir_sha256(estado: &mut [u32; 8], bloco: &[u8]) {
+    let mut w = [0u32; 64];
+    for (t, palavra) in bloco.chunks_exact(4).enumerate() {
+        w[t] = u32::from_be_bytes([palavra[0], palavra[1], palavra[2], palavra[3]]);
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *estado;
+    for t in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K256[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (valor, novo) in estado.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *valor = valor.wrapping_add(novo);
+    }
+}
+
+/// SHA-256 (FIPS 180-4).
+#[derive(Clone)]
+pub struct Sha256(NucleoSha256);
+
+impl FuncaoHash for Sha256 {
+    const TAMANHO_SAIDA: usize = 32;
+    const TAMANHO_BLOCO: usize = 64;
+
+    fn new() -> Self {
+        Sha256(NucleoSha256::new(H256))
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        self.0.update(dados);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::TAMANHO_SAIDA)
+    }
+}
+
+/// SHA-224 (FIPS 180-4): SHA-256 com outro valor inicial, truncado a 28 bytes.
+#[derive(Clone)]
+pub struct Sha224(NucleoSha256);
+
+impl FuncaoHash for Sha224 {
+    const TAMANHO_SAIDA: usize = 28;
+    const TAMANHO_BLOCO: usize = 64;
+
+    fn new() -> Self {
+        Sha224(NucleoSha256::new(H224))
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        self.0.update(dados);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::TAMANHO_SAIDA)
+    }
+}
+
+/// Constantes de rodada do SHA-512 (FIPS 180-4, seção 4.2.3).
+const K512: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+/// Valor inicial do SHA-512 (FIPS 180-4, seção 5.3.5).
+const H512: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// Valor inicial do SHA-384 (FIPS 180-4, seção 5.3.4).
+const H384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+/// Núcleo comum ao SHA-512 e ao SHA-384, que diferem apenas no valor
+/// inicial e no truncamento da saída.
+#[derive(Clone)]
+struct NucleoSha512 {
+    estado: [u64; 8],
+    acumulador: Acumulador<128>,
+}
+
+impl NucleoSha512 {
+    fn new(valor_inicial: [u64; 8]) -> Self {
+        NucleoSha512 {
+            estado: valor_inicial,
+            acumulador: Acumulador::new(),
+        }
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        let estado = &mut self.estado;
+        self.acumulador
+            .alimentar(dados, |bloco| comprimir_sha512(estado, bloco));
+    }
+
+    fn finalize(mut self, tamanho_saida: usize) -> Vec<u8> {
+        let estado = &mut self.estado;
+        self.acumulador
+            .preencher(16, |bloco| comprimir_sha512(estado, bloco));
+        let mut resumo: Vec<u8> = self.estado.iter().flat_map(|v| v.to_be_bytes()).collect();
+        resumo.truncate(tamanho_saida);
+        resumo
+    }
+}
+
+fn comprimir_sha512(estado: &mut [u64; 8], bloco: &[u8]) {
+    let mut w = [0u64; 80];
+    for (t, palavra) in bloco.chunks_exact(8).enumerate() {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(palavra);
+        w[t] = u64::from_be_bytes(bytes);
+    }
+    for t in 16..80 {
+        let s0 = w[t - 15].rotate_right(1) ^ w[t - 15].rotate_right(8) ^ (w[t - 15] >> 7);
+        let s1 = w[t - 2].rotate_right(19) ^ w[t - 2].rotate_right(61) ^ (w[t - 2] >> 6);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *estado;
+    for t in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K512[t])
+            .wrapping_add(w[t]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+
+    for (valor, novo) in estado.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *valor = valor.wrapping_add(novo);
+    }
+}
+
+/// SHA-512 (FIPS 180-4).
+#[derive(Clone)]
+pub struct Sha512(NucleoSha512);
+
+impl FuncaoHash for Sha512 {
+    const TAMANHO_SAIDA: usize = 64;
+    const TAMANHO_BLOCO: usize = 128;
+
+    fn new() -> Self {
+        Sha512(NucleoSha512::new(H512))
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        self.0.update(dados);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::TAMANHO_SAIDA)
+    }
+}
+
+/// SHA-384 (FIPS 180-4): SHA-512 com outro valor inicial, truncado a 48 bytes.
+#[derive(Clone)]
+pub struct Sha384(NucleoSha512);
+
+impl FuncaoHash for Sha384 {
+    const TAMANHO_SAIDA: usize = 48;
+    const TAMANHO_BLOCO: usize = 128;
+
+    fn new() -> Self {
+        Sha384(NucleoSha512::new(H384))
+    }
+
+    fn update(&mut self, dados: &[u8]) {
+        self.0.update(dados);
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        self.0.finalize(Self::TAMANHO_SAIDA)
+    }
+}
+
+/// HMAC (RFC 2104) com a função de hash `H`.
+pub fn hmac<H: FuncaoHash>(chave: &[u8], dados: &[u8]) -> Vec<u8> {
+    let mut chave_bloco = if chave.len() > H::TAMANHO_BLOCO {
+        H::digest(chave)
+    } else {
+        chave.to_vec()
+    };
+    chave_bloco.resize(H::TAMANHO_BLOCO, 0);
+
+    let mut interno = H::new();
+    interno.update(&chave_bloco.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+    interno.update(dados);
+    let resumo_interno = interno.finalize();
+
+    let mut externo = H::new();
+    externo.update(&chave_bloco.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+    externo.update(&resumo_interno);
+    externo.finalize()
+}
+
+/// MGF1 (RFC 8017, apêndice B.2.1): gera `tamanho` bytes concatenando
+/// H(semente || contador) para contador = 0, 1, ... em 4 bytes big-endian.
+pub fn mgf1<H: FuncaoHash>(semente: &[u8], tamanho: usize) -> Vec<u8> {
+    let mut mascara = Vec::with_capacity(tamanho + H::TAMANHO_SAIDA);
+
+    let mut contador: u32 = 0;
+    while mascara.len() < tamanho {
+        let mut hash = H::new();
+        hash.update(semente);
+        hash.update(&contador.to_be_bytes());
+        mascara.extend(hash.finalize());
+        contador += 1;
+    }
+    mascara.truncate(tamanho);
+    mascara
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex_para_octetos;
+
+    const MENSAGEM_448: &[u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    const MENSAGEM_896: &[u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                                  hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+    /// Confere os resumos de "abc", das mensagens de 448 e 896 bits e de um
+    /// milhão de 'a' (este alimentado em pedaços de 1000 bytes).
+    fn conferir_vetores<H: FuncaoHash>(esperados: [&str; 4]) {
+        assert_eq!(H::digest(b"abc"), hex_para_octetos(esperados[0]));
+        assert_eq!(H::digest(MENSAGEM_448), hex_para_octetos(esperados[1]));
+        assert_eq!(H::digest(MENSAGEM_896), hex_para_octetos(esperados[2]));
+
+        let mut hash = H::new();
+        for _ in 0..1000 {
+            hash.update(&[b'a'; 1000]);
+        }
+        assert_eq!(hash.finalize(), hex_para_octetos(esperados[3]));
+    }
+
+    #[test]
+    fn vetores_fips_180_4_sha1() {
+        conferir_vetores::<Sha1>([
+            "a9993e364706816aba3e25717850c26c9cd0d89d",
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+            "a49b2446a02c645bf419f995b67091253a04a259",
+            "34aa973cd4c4daa4f61eeb2bdbad27316534016f",
+        ]);
+    }
+
+    #[test]
+    fn vetores_fips_180_4_sha224() {
+        conferir_vetores::<Sha224>([
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525",
+            "c97ca9a559850ce97a04a96def6d99a9e0e0e2ab14e6b8df265fc0b3",
+            "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67",
+        ]);
+    }
+
+    #[test]
+    fn vetores_fips_180_4_sha256() {
+        conferir_vetores::<Sha256>([
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0",
+        ]);
+    }
+
+    #[test]
+    fn vetores_fips_180_4_sha384() {
+        conferir_vetores::<Sha384>([
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+             8086072ba1e7cc2358baeca134c825a7",
+            "3391fdddfc8dc7393707a65b1b4709397cf8b1d162af05abfe8f450de5f36bc6\
+             b0455a8520bc4e6f5fe95b1fe3c8452b",
+            "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712\
+             fcc7c71a557e2db966c3e9fa91746039",
+            "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b\
+             07b8b3dc38ecc4ebae97ddd87f3d8985",
+        ]);
+    }
+
+    #[test]
+    fn vetores_fips_180_4_sha512() {
+        conferir_vetores::<Sha512>([
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+            "204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c335\
+             96fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445",
+            "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+             501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909",
+            "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb\
+             de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b",
+        ]);
+    }
+
+    /// Chaves e dados dos casos de teste 1 a 7 da RFC 4231.
+    fn casos_rfc_4231() -> [(Vec<u8>, Vec<u8>); 7] {
+        [
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            ((0x01..=0x19).collect(), vec![0xcd; 50]),
+            (vec![0x0c; 20], b"Test With Truncation".to_vec()),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger \
+                  than block-size data. The key needs to be hashed before being \
+                  used by the HMAC algorithm."
+                    .to_vec(),
+            ),
+        ]
+    }
+
+    /// O caso 5 confere só os primeiros 128 bits, como na RFC.
+    fn conferir_hmac<H: FuncaoHash>(esperados: [&str; 7]) {
+        for (i, ((chave, dados), esperado)) in casos_rfc_4231().iter().zip(esperados).enumerate() {
+            let mut resultado = hmac::<H>(chave, dados);
+            if i == 4 {
+                resultado.truncate(16);
+            }
+            assert_eq!(resultado, hex_para_octetos(esperado), "caso {}", i + 1);
+        }
+    }
+
+    #[test]
+    fn vetores_rfc_4231_hmac_sha224() {
+        conferir_hmac::<Sha224>([
+            "896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22",
+            "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44",
+            "7fb3cb3588c6c1f6ffa9694d7d6ad2649365b0c1f65d69d1ec8333ea",
+            "6c11506874013cac6a2abc1bb382627cec6a90d86efc012de7afec5a",
+            "0e2aea68a90c8d37c988bcdb9fca6fa8",
+            "95e9a0db962095adaebe9b2d6f0dbce2d499f112f2d2b7273fa6870e",
+            "3a854166ac5d9f023f54d517d0b39dbd946770db9c2b95c9f6f565d1",
+        ]);
+    }
+
+    #[test]
+    fn vetores_rfc_4231_hmac_sha256() {
+        conferir_hmac::<Sha256>([
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            "a3b6167473100ee06e0c796c2955552b",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+        ]);
+    }
+
+    #[test]
+    fn vetores_rfc_4231_hmac_sha384() {
+        conferir_hmac::<Sha384>([
+            "afd03944d84895626b0825f4ab46907f15f9dadbe4101ec682aa034c7cebc59c\
+             faea9ea9076ede7f4af152e8b2fa9cb6",
+            "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e\
+             8e2240ca5e69e2c78b3239ecfab21649",
+            "88062608d3e6ad8a0aa2ace014c8a86f0aa635d947ac9febe83ef4e55966144b\
+             2a5ab39dc13814b94e3ab6e101a34f27",
+            "3e8a69b7783c25851933ab6290af6ca77a9981480850009cc5577c6e1f573b4e\
+             6801dd23c4a7d679ccf8a386c674cffb",
+            "3abf34c3503b2a23a46efc619baef897",
+            "4ece084485813e9088d2c63a041bc5b44f9ef1012a2b588f3cd11f05033ac4c6\
+             0c2ef6ab4030fe8296248df163f44952",
+            "6617178e941f020d351e2f254e8fd32c602420feb0b8fb9adccebb82461e99c5\
+             a678cc31e799176d3860e6110c46523e",
+        ]);
+    }
+
+    #[test]
+    fn vetores_rfc_4231_hmac_sha512() {
+        conferir_hmac::<Sha512>([
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+             daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+            "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39\
+             bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb",
+            "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db\
+             a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd",
+            "415fad6271580a531d4179bc891d87a6",
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+             6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+            "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944\
+             b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58",
+        ]);
+    }
+}
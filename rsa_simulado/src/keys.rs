@@ -0,0 +1,1074 @@
+// --------------------------------------------------------
+// Geração de Chaves
+// --------------------------------------------------------
+
+use crate::arithmetic::{
+    exponenciacao_modular, exponenciacao_modular_tempo_constante, inverso_modular, mdc_binario,
+};
+use crate::blinding::CacheCegamento;
+use crate::error::RsaError;
+use crate::fips::{self, RelatorioFips};
+use crate::primes::especiais::TipoPrimo;
+use crate::primes::{eh_primo_com_teste, TestePrimalidade};
+use crate::random::RandomSource;
+use num::Integer;
+use num_bigint::BigInt;
+use num_traits::One;
+use std::fmt;
+use std::str::FromStr;
+
+/// Expoente público padrão (2^16 + 1).
+pub const EXPOENTE_PUBLICO_PADRAO: u32 = 65537;
+
+/// Menor módulo aceito por `KeyPair::generate`, em bits.
+pub const TAMANHO_MINIMO_MODULO: u32 = 16;
+
+/// Maior quantidade de fatores primos aceita na geração de chaves.
+pub const QUANTIDADE_MAXIMA_PRIMOS: u32 = 5;
+
+/// Quantos fatores primos um módulo de `bits` bits pode ter sem que algum
+/// deles fique pequeno demais (os mesmos limites do OpenSSL): 2 abaixo de
+/// 1024 bits, 3 abaixo de 4096, 4 abaixo de 8192 e 5 a partir daí.
+pub fn quantidade_maxima_primos(bits: u32) -> u32 {
+    match bits {
+        0..=1023 => 2,
+        1024..=4095 => 3,
+        4096..=8191 => 4,
+        _ => QUANTIDADE_MAXIMA_PRIMOS,
+    }
+}
+
+/// Função de n usada como módulo no cálculo do expoente privado d.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Totiente {
+    /// φ(n) = (p − 1)(q − 1), como no artigo original do RSA.
+    Euler,
+    /// λ(n) = mmc(p − 1, q − 1), exigida pelo FIPS 186-5; produz o menor d válido.
+    #[default]
+    Carmichael,
+}
+
+impl Totiente {
+    /// Valor da função para os primos `p` e `q`.
+    pub fn calcular(self, p: &BigInt, q: &BigInt) -> BigInt {
+        match self {
+            Totiente::Euler => funcao_totiente(p, q),
+            Totiente::Carmichael => funcao_carmichael(p, q),
+        }
+    }
+
+    /// Valor da função para um n com os fatores primos distintos `primos`:
+    /// o produto (φ) ou o mmc (λ) dos r_i − 1.
+    pub fn calcular_primos(self, primos: &[BigInt]) -> BigInt {
+        let menos_1 = primos.iter().map(|primo| primo - 1);
+        match self {
+            Totiente::Euler => menos_1.product(),
+            Totiente::Carmichael => menos_1.fold(BigInt::one(), |mmc, r| mmc.lcm(&r)),
+        }
+    }
+}
+
+/// Escolhas usuais de expoente público.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ExpoentePublico {
+    /// e = 3: cifragem mais rápida, mas frágil sem preenchimento (ataque de Håstad).
+    Tres,
+    /// e = 17.
+    Dezessete,
+    /// e = 65537 (F4), o padrão.
+    #[default]
+    F4,
+    /// Qualquer outro valor ímpar maior que 1.
+    Personalizado(BigInt),
+}
+
+impl ExpoentePublico {
+    /// Valor numérico do expoente.
+    pub fn valor(&self) -> BigInt {
+        match self {
+            ExpoentePublico::Tres => BigInt::from(3),
+            ExpoentePublico::Dezessete => BigInt::from(17),
+            ExpoentePublico::F4 => BigInt::from(EXPOENTE_PUBLICO_PADRAO),
+            ExpoentePublico::Personalizado(e) => e.clone(),
+        }
+    }
+}
+
+impl FromStr for ExpoentePublico {
+    type Err = RsaError;
+
+    /// Interpreta um número decimal; 3, 17 e 65537 viram as variantes nomeadas.
+    /// Retorna `RsaError::InvalidKey` se o texto não for um inteiro ímpar
+    /// maior que 1.
+    fn from_str(texto: &str) -> Result<Self, RsaError> {
+        let e: BigInt = texto.trim().parse().map_err(|_| RsaError::InvalidKey)?;
+        validar_expoente_publico(&e, false)?;
+        Ok(match u32::try_from(&e) {
+            Ok(3) => ExpoentePublico::Tres,
+            Ok(17) => ExpoentePublico::Dezessete,
+            Ok(EXPOENTE_PUBLICO_PADRAO) => ExpoentePublico::F4,
+            _ => ExpoentePublico::Personalizado(e),
+        })
+    }
+}
+
+/// Opções de `KeyPair::generate_com_opcoes` e `RsaPrivateKey::a_partir_dos_primos_com_opcoes`.
+#[derive(Debug, Clone)]
+pub struct OpcoesGeracao {
+    /// Módulo usado no cálculo de d (padrão: λ(n)).
+    pub totiente: Totiente,
+    /// Segue o FIPS 186-5: exige 2^16 < e < 2^256 (o que exclui e = 3 e
+    /// e = 17) e, na geração, todos os requisitos de `fips::gerar_chaves`.
+    pub modo_estrito: bool,
+    /// Tipo dos primos p e q (padrão: aleatórios comuns). Primos seguros e
+    /// fortes têm tamanhos mínimos próprios (ver `primes::especiais`). Ignorado
+    /// no modo estrito, que segue os primos prováveis do Apêndice A.1.3.
+    pub tipo_primo: TipoPrimo,
+    /// Quantidade de fatores primos de n na geração (padrão: 2). Acima de 2,
+    /// a chave é multiprimo (RFC 8017, seção 3.2) e o tamanho pedido precisa
+    /// admiti-la (ver `quantidade_maxima_primos`); o modo estrito exige 2.
+    pub quantidade_primos: u32,
+    /// Teste de primalidade aplicado aos primos sorteados e aos recebidos
+    /// por `a_partir_dos_primos_com_opcoes` (padrão: Miller–Rabin com
+    /// `RODADAS_MILLER_RABIN` rodadas). Ignorado na geração do modo estrito,
+    /// que usa as rodadas da tabela B.1 do FIPS 186-5.
+    pub teste: TestePrimalidade,
+}
+
+impl Default for OpcoesGeracao {
+    fn default() -> Self {
+        OpcoesGeracao {
+            totiente: Totiente::default(),
+            modo_estrito: false,
+            tipo_primo: TipoPrimo::default(),
+            quantidade_primos: 2,
+            teste: TestePrimalidade::default(),
+        }
+    }
+}
+
+/// Chave pública RSA: módulo `n` e expoente público `e`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub n: BigInt,
+    pub e: BigInt,
+}
+
+/// Chave privada RSA com os parâmetros do CRT já pré-calculados.
+///
+/// O `Debug` mostra apenas a parte pública; `d`, `p`, `q`, `dp`, `dq`,
+/// `qinv` e os demais primos nunca aparecem em logs. A chave também guarda o
+/// par de cegamento usado por `aplicar_expoente_privado`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RsaPrivateKey {
+    n: BigInt,
+    e: BigInt,
+    d: BigInt,
+    p: BigInt,
+    q: BigInt,
+    dp: BigInt,
+    dq: BigInt,
+    qinv: BigInt,
+    outros_primos: Vec<InfoOutroPrimo>,
+    cegamento: CacheCegamento,
+}
+
+/// Fator primo r_i (i ≥ 3) de uma chave multiprimo, o `OtherPrimeInfo` da
+/// RFC 8017: d_i = d mod (r_i − 1) e t_i = (r_1 · … · r_(i−1))⁻¹ mod r_i.
+#[derive(Clone, PartialEq, Eq)]
+pub struct InfoOutroPrimo {
+    r: BigInt,
+    d: BigInt,
+    t: BigInt,
+}
+
+impl InfoOutroPrimo {
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+
+    pub fn d(&self) -> &BigInt {
+        &self.d
+    }
+
+    pub fn t(&self) -> &BigInt {
+        &self.t
+    }
+}
+
+/// Par de chaves gerado por `KeyPair::generate`.
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub publica: RsaPublicKey,
+    pub privada: RsaPrivateKey,
+}
+
+impl RsaPublicKey {
+    /// Calcula m^e mod n, rejeitando blocos fora do intervalo [0, n).
+    pub fn aplicar_expoente_publico(&self, m: &BigInt) -> Result<BigInt, RsaError> {
+        if *m < BigInt::from(0) {
+            return Err(RsaError::InvalidBlock);
+        }
+        if *m >= self.n {
+            return Err(RsaError::MessageTooLong);
+        }
+        exponenciacao_modular(m, &self.e, &self.n)
+    }
+}
+
+impl RsaPrivateKey {
+    /// Monta a chave privada a partir dos primos `p` e `q` e do expoente público `e`,
+    /// com d calculado módulo λ(n).
+    ///
+    /// Retorna `RsaError::InvalidKey` se `p` e `q` não forem primos ímpares distintos,
+    /// se `e` não for ímpar e maior que 1 (ou estiver fora da faixa do modo
+    /// estrito) ou se d ≤ 2^(nlen/2), e
+    /// `RsaError::NotInvertible` se gcd(e, λ(n)) ≠ 1.
+    pub fn a_partir_dos_primos(p: BigInt, q: BigInt, e: BigInt) -> Result<Self, RsaError> {
+        RsaPrivateKey::a_partir_dos_primos_com_opcoes(p, q, e, &OpcoesGeracao::default())
+    }
+
+    /// Variante de `a_partir_dos_primos` que segue as `opcoes` dadas.
+    ///
+    /// No modo estrito, a chave também precisa passar por
+    /// `RelatorioFips::auditar`; caso contrário, retorna `RsaError::InvalidKey`.
+    pub fn a_partir_dos_primos_com_opcoes(
+        p: BigInt,
+        q: BigInt,
+        e: BigInt,
+        opcoes: &OpcoesGeracao,
+    ) -> Result<Self, RsaError> {
+        RsaPrivateKey::a_partir_de_varios_primos_com_opcoes(vec![p, q], e, opcoes)
+    }
+
+    /// Monta uma chave com n = r_1 · r_2 · … · r_k a partir dos `primos`
+    /// (k ≥ 2), na ordem dada: r_1 e r_2 fazem o papel de p e q, e os demais
+    /// viram `outros_primos`. d é calculado módulo λ(n).
+    ///
+    /// Retorna os mesmos erros de `a_partir_dos_primos`, estendidos a todos
+    /// os primos, e `RsaError::InvalidKey` se houver menos de dois.
+    pub fn a_partir_de_varios_primos(primos: Vec<BigInt>, e: BigInt) -> Result<Self, RsaError> {
+        RsaPrivateKey::a_partir_de_varios_primos_com_opcoes(primos, e, &OpcoesGeracao::default())
+    }
+
+    /// Variante de `a_partir_de_varios_primos` que segue as `opcoes` dadas;
+    /// `opcoes.quantidade_primos` é ignorada em favor de `primos.len()`.
+    pub fn a_partir_de_varios_primos_com_opcoes(
+        primos: Vec<BigInt>,
+        e: BigInt,
+        opcoes: &OpcoesGeracao,
+    ) -> Result<Self, RsaError> {
+        validar_expoente_publico(&e, opcoes.modo_estrito)?;
+        let distintos = primos
+            .iter()
+            .enumerate()
+            .all(|(i, primo)| !primos[..i].contains(primo));
+        if primos.len() < 2
+            || !distintos
+            || primos
+                .iter()
+                .any(|primo| !primo.bit(0) || !eh_primo_com_teste(primo, opcoes.teste))
+        {
+            return Err(RsaError::InvalidKey);
+        }
+
+        validar_expoente_primos(&e, &primos)?;
+        let d = expoente_privado_fips(&e, &primos, opcoes.totiente)?;
+        let chave = RsaPrivateKey::montar_primos(primos, e, d)?;
+
+        if opcoes.modo_estrito {
+            exigir_carmichael(opcoes)?;
+            if !RelatorioFips::auditar(&chave).aprovado() {
+                return Err(RsaError::InvalidKey);
+            }
+        }
+        Ok(chave)
+    }
+
+    /// Monta a chave sem validar os primos, pré-calculando
+    /// dp = d mod (p − 1), dq = d mod (q − 1) e qinv = q⁻¹ mod p.
+    pub(crate) fn montar(p: BigInt, q: BigInt, e: BigInt, d: BigInt) -> Result<Self, RsaError> {
+        RsaPrivateKey::montar_primos(vec![p, q], e, d)
+    }
+
+    /// Variante de `montar` para dois ou mais primos, que também calcula
+    /// d_i e t_i de cada primo além de p e q.
+    pub(crate) fn montar_primos(
+        primos: Vec<BigInt>,
+        e: BigInt,
+        d: BigInt,
+    ) -> Result<Self, RsaError> {
+        let mut primos = primos.into_iter();
+        let (Some(p), Some(q)) = (primos.next(), primos.next()) else {
+            return Err(RsaError::InvalidKey);
+        };
+        let dp = &d % (&p - 1);
+        let dq = &d % (&q - 1);
+        let qinv = inverso_modular(&q, &p)?;
+
+        let mut n = &p * &q;
+        let mut outros_primos = Vec::new();
+        for r in primos {
+            outros_primos.push(InfoOutroPrimo {
+                d: &d % (&r - 1),
+                t: inverso_modular(&n, &r)?,
+                r: r.clone(),
+            });
+            n *= r;
+        }
+
+        Ok(RsaPrivateKey {
+            n,
+            e,
+            d,
+            p,
+            q,
+            dp,
+            dq,
+            qinv,
+            outros_primos,
+            cegamento: CacheCegamento::default(),
+        })
+    }
+
+    /// Parte pública correspondente a esta chave.
+    pub fn chave_publica(&self) -> RsaPublicKey {
+        RsaPublicKey {
+            n: self.n.clone(),
+            e: self.e.clone(),
+        }
+    }
+
+    /// Calcula c^d mod n pelo Teorema Chinês do Resto, com a recombinação de Garner:
+    ///
+    /// m1 = c^dp mod p, m2 = c^dq mod q, h = qinv * (m1 − m2) mod p, m = m2 + h * q.
+    ///
+    /// Nas chaves multiprimo, a recombinação continua pelos demais primos
+    /// (RFC 8017, seção 5.1.2): com R = r_1 · … · r_(i−1), m_i = c^d_i mod r_i,
+    /// h = (m_i − m) · t_i mod r_i e m = m + R · h.
+    ///
+    /// As duas exponenciações usam módulos e expoentes com metade do tamanho,
+    /// o que torna a operação até 4x mais rápida que `aplicar_expoente_privado_sem_crt`.
+    /// Ambas usam a escada de Montgomery, em tempo constante em dp e dq, e o
+    /// bloco é cegado com um r aleatório antes da exponenciação (ver
+    /// `crate::blinding`). Retorna `RsaError::InvalidBlock` se `c` estiver
+    /// fora de [0, n).
+    pub fn aplicar_expoente_privado(&self, c: &BigInt) -> Result<BigInt, RsaError> {
+        self.validar_bloco(c)?;
+        self.cegamento
+            .aplicar(c, &self.n, &self.e, |cegado| self.aplicar_crt(cegado))
+    }
+
+    /// Variante de `aplicar_expoente_privado` que, quando o par de cegamento
+    /// precisa ser sorteado, usa `rng` como fonte de aleatoriedade.
+    pub fn aplicar_expoente_privado_com_rng<R: RandomSource + ?Sized>(
+        &self,
+        c: &BigInt,
+        rng: &mut R,
+    ) -> Result<BigInt, RsaError> {
+        self.validar_bloco(c)?;
+        self.cegamento
+            .aplicar_com_rng(c, &self.n, &self.e, rng, |cegado| self.aplicar_crt(cegado))
+    }
+
+    fn aplicar_crt(&self, c: &BigInt) -> Result<BigInt, RsaError> {
+        let m1 = exponenciacao_modular_tempo_constante(c, &self.dp, &self.p)?;
+        let m2 = exponenciacao_modular_tempo_constante(c, &self.dq, &self.q)?;
+
+        let mut h = (&self.qinv * (m1 - &m2)) % &self.p;
+        if h < BigInt::from(0) {
+            h += &self.p;
+        }
+        let mut m = m2 + h * &self.q;
+
+        let mut produto = &self.p * &self.q;
+        for outro in &self.outros_primos {
+            let m_i = exponenciacao_modular_tempo_constante(c, &outro.d, &outro.r)?;
+            let h = ((m_i - &m) * &outro.t).mod_floor(&outro.r);
+            m += &produto * h;
+            produto *= &outro.r;
+        }
+        Ok(m)
+    }
+
+    /// Calcula c^d mod n diretamente sobre o módulo completo (referência sem
+    /// CRT e sem cegamento).
+    pub fn aplicar_expoente_privado_sem_crt(&self, c: &BigInt) -> Result<BigInt, RsaError> {
+        self.validar_bloco(c)?;
+        exponenciacao_modular_tempo_constante(c, &self.d, &self.n)
+    }
+
+    fn validar_bloco(&self, c: &BigInt) -> Result<(), RsaError> {
+        if *c < BigInt::from(0) || *c >= self.n {
+            return Err(RsaError::InvalidBlock);
+        }
+        Ok(())
+    }
+
+    pub fn n(&self) -> &BigInt {
+        &self.n
+    }
+
+    pub fn e(&self) -> &BigInt {
+        &self.e
+    }
+
+    pub fn d(&self) -> &BigInt {
+        &self.d
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn q(&self) -> &BigInt {
+        &self.q
+    }
+
+    pub fn dp(&self) -> &BigInt {
+        &self.dp
+    }
+
+    pub fn dq(&self) -> &BigInt {
+        &self.dq
+    }
+
+    pub fn qinv(&self) -> &BigInt {
+        &self.qinv
+    }
+
+    /// Primos r_3, …, r_k de uma chave multiprimo (vazio com dois primos).
+    pub fn outros_primos(&self) -> &[InfoOutroPrimo] {
+        &self.outros_primos
+    }
+
+    /// Quantidade total de fatores primos de n.
+    pub fn quantidade_primos(&self) -> usize {
+        2 + self.outros_primos.len()
+    }
+}
+
+impl fmt::Debug for RsaPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaPrivateKey")
+            .field("n", &self.n)
+            .field("e", &self.e)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyPair {
+    /// Gera um par de chaves com módulo de `bits` bits e expoente público `e`,
+    /// com d calculado módulo λ(n).
+    ///
+    /// Cada primo é sorteado por `gerar_primo` de novo enquanto
+    /// gcd(e, p − 1) ≠ 1, e o par é descartado se p = q ou d ≤ 2^(nlen/2).
+    ///
+    /// Retorna `RsaError::InvalidBitLength` se `bits < 16` e
+    /// `RsaError::InvalidKey` se `e` não for ímpar e maior que 1 (ou, no modo
+    /// estrito, estiver fora de 2^16 < e < 2^256).
+    pub fn generate(bits: u32, e: &BigInt) -> Result<KeyPair, RsaError> {
+        KeyPair::generate_com_opcoes(bits, e, &OpcoesGeracao::default())
+    }
+
+    /// Variante de `generate` que segue as `opcoes` dadas.
+    ///
+    /// No modo estrito, delega a `fips::gerar_chaves`, que exige 2048, 3072
+    /// ou 4096 bits e d módulo λ(n) (senão, `RsaError::InvalidKey`).
+    ///
+    /// Com `opcoes.quantidade_primos` = k > 2, os `bits` são divididos entre
+    /// os k primos, que são sorteados de novo até n ter exatamente `bits`
+    /// bits. Retorna `RsaError::InvalidKey` se k < 2, se k passar de
+    /// `quantidade_maxima_primos(bits)` ou, no modo estrito, se k ≠ 2.
+    pub fn generate_com_opcoes(
+        bits: u32,
+        e: &BigInt,
+        opcoes: &OpcoesGeracao,
+    ) -> Result<KeyPair, RsaError> {
+        KeyPair::generate_com_rng(bits, e, opcoes, &mut rand::thread_rng())
+    }
+
+    /// Variante de `generate_com_opcoes` que sorteia os primos a partir de
+    /// `rng`: com um `random::HmacDrbg` de semente fixa, o par gerado é
+    /// sempre o mesmo.
+    pub fn generate_com_rng<R: RandomSource + ?Sized>(
+        bits: u32,
+        e: &BigInt,
+        opcoes: &OpcoesGeracao,
+        rng: &mut R,
+    ) -> Result<KeyPair, RsaError> {
+        let k = opcoes.quantidade_primos;
+        if bits < TAMANHO_MINIMO_MODULO {
+            return Err(RsaError::InvalidBitLength(bits));
+        }
+        if k < 2 || k > quantidade_maxima_primos(bits) {
+            return Err(RsaError::InvalidKey);
+        }
+        validar_expoente_publico(e, opcoes.modo_estrito)?;
+        if opcoes.modo_estrito {
+            exigir_carmichael(opcoes)?;
+            if k != 2 {
+                return Err(RsaError::InvalidKey);
+            }
+            return fips::gerar_chaves_com_rng(bits, e, rng).map(|(chaves, _)| chaves);
+        }
+
+        // Os bits são divididos igualmente, e os primeiros primos ficam com a sobra
+        let tamanhos: Vec<u32> = (0..k).map(|i| bits / k + u32::from(i < bits % k)).collect();
+        loop {
+            let mut primos = Vec::with_capacity(tamanhos.len());
+            for &tamanho in &tamanhos {
+                primos.push(gerar_primo_coprimo(
+                    tamanho,
+                    e,
+                    opcoes.tipo_primo,
+                    opcoes.teste,
+                    rng,
+                )?);
+            }
+
+            // Com dois primos, os dois bits altos ligados já garantem o tamanho de n
+            let n: BigInt = primos.iter().product();
+            let distintos = primos
+                .iter()
+                .enumerate()
+                .all(|(i, primo)| !primos[..i].contains(primo));
+            if n.bits() != u64::from(bits) || !distintos {
+                continue;
+            }
+
+            let d = match expoente_privado_fips(e, &primos, opcoes.totiente) {
+                Ok(d) => d,
+                Err(RsaError::NotInvertible | RsaError::InvalidKey) => continue,
+                Err(erro) => return Err(erro),
+            };
+
+            let privada = RsaPrivateKey::montar_primos(primos, e.clone(), d)?;
+            let publica = privada.chave_publica();
+            return Ok(KeyPair { publica, privada });
+        }
+    }
+}
+
+/// Exige `e` ímpar e maior que 1 e, no modo estrito, 2^16 < e < 2^256.
+pub(crate) fn validar_expoente_publico(e: &BigInt, modo_estrito: bool) -> Result<(), RsaError> {
+    if *e <= BigInt::one() || !e.bit(0) {
+        return Err(RsaError::InvalidKey);
+    }
+    if modo_estrito && (e.bits() <= 16 || e.bits() > 256) {
+        return Err(RsaError::InvalidKey);
+    }
+    Ok(())
+}
+
+/// O FIPS 186-5 define d módulo λ(n); o modo estrito não aceita φ(n).
+fn exigir_carmichael(opcoes: &OpcoesGeracao) -> Result<(), RsaError> {
+    if opcoes.totiente != Totiente::Carmichael {
+        return Err(RsaError::InvalidKey);
+    }
+    Ok(())
+}
+
+/// Confere se `e` é invertível módulo φ(n) ou λ(n).
+///
+/// Como φ(n) e λ(n) têm os mesmos fatores primos, basta gcd(e, p − 1) =
+/// gcd(e, q − 1) = 1. Retorna `RsaError::NotInvertible` caso contrário.
+pub fn validar_expoente(e: &BigInt, p: &BigInt, q: &BigInt) -> Result<(), RsaError> {
+    validar_expoente_primos(e, &[p.clone(), q.clone()])
+}
+
+/// Variante de `validar_expoente` para os fatores primos de uma chave
+/// multiprimo: exige gcd(e, r_i − 1) = 1 para todo i.
+pub fn validar_expoente_primos(e: &BigInt, primos: &[BigInt]) -> Result<(), RsaError> {
+    if !primos
+        .iter()
+        .all(|primo| mdc_binario(e, &(primo - 1)).is_one())
+    {
+        return Err(RsaError::NotInvertible);
+    }
+    Ok(())
+}
+
+/// Sorteia primos do `tipo` dado, com `bits` bits e aprovados pelo `teste`,
+/// até obter um p com gcd(e, p − 1) = 1.
+pub(crate) fn gerar_primo_coprimo<R: RandomSource + ?Sized>(
+    bits: u32,
+    e: &BigInt,
+    tipo: TipoPrimo,
+    teste: TestePrimalidade,
+    rng: &mut R,
+) -> Result<BigInt, RsaError> {
+    loop {
+        let p = tipo.gerar_com_teste(bits, teste, rng)?;
+        if mdc_binario(e, &(&p - 1)).is_one() {
+            return Ok(p);
+        }
+    }
+}
+
+/// Função totiente de Euler: φ(n) = (p − 1)(q − 1).
+pub fn funcao_totiente(p: &BigInt, q: &BigInt) -> BigInt {
+    (p - 1) * (q - 1)
+}
+
+/// Função de Carmichael: λ(n) = mmc(p − 1, q − 1), divisor de φ(n).
+pub fn funcao_carmichael(p: &BigInt, q: &BigInt) -> BigInt {
+    let p_menos_1: BigInt = p - 1;
+    p_menos_1.lcm(&(q - 1))
+}
+
+/// Expoente privado d tal que e * d ≡ 1 (mod φ(n)).
+pub fn expoente_privado(e: &BigInt, phi_n: &BigInt) -> Result<BigInt, RsaError> {
+    inverso_modular(e, phi_n)
+}
+
+/// Expoente privado módulo φ(n) ou λ(n), com a exigência do FIPS 186-5 de que
+/// d > 2^(nlen/2), que afasta os ataques a d pequeno (Wiener, Boneh–Durfee).
+///
+/// Retorna `RsaError::InvalidKey` se d for pequeno demais.
+fn expoente_privado_fips(
+    e: &BigInt,
+    primos: &[BigInt],
+    totiente: Totiente,
+) -> Result<BigInt, RsaError> {
+    let d = expoente_privado(e, &totiente.calcular_primos(primos))?;
+    let nlen = primos.iter().product::<BigInt>().bits();
+    if d <= BigInt::one() << (nlen / 2) {
+        return Err(RsaError::InvalidKey);
+    }
+    Ok(d)
+}
+
+/// Expoentes privados calculados com φ(n) e com λ(n), para comparação didática.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComparacaoExpoentes {
+    pub phi: BigInt,
+    pub lambda: BigInt,
+    pub d_phi: BigInt,
+    pub d_lambda: BigInt,
+}
+
+impl ComparacaoExpoentes {
+    /// Calcula os dois expoentes privados para `p`, `q` e `e`.
+    ///
+    /// Retorna `RsaError::NotInvertible` se `e` não for invertível módulo λ(n).
+    pub fn calcular(p: &BigInt, q: &BigInt, e: &BigInt) -> Result<Self, RsaError> {
+        ComparacaoExpoentes::calcular_primos(&[p.clone(), q.clone()], e)
+    }
+
+    /// Variante de `calcular` para todos os fatores primos de n, inclusive
+    /// os de uma chave multiprimo.
+    pub fn calcular_primos(primos: &[BigInt], e: &BigInt) -> Result<Self, RsaError> {
+        let phi = Totiente::Euler.calcular_primos(primos);
+        let lambda = Totiente::Carmichael.calcular_primos(primos);
+        Ok(ComparacaoExpoentes {
+            d_phi: expoente_privado(e, &phi)?,
+            d_lambda: expoente_privado(e, &lambda)?,
+            phi,
+            lambda,
+        })
+    }
+}
+
+impl fmt::Display for ComparacaoExpoentes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<10} {:>8} {:>8}", "", "φ(n)", "λ(n)")?;
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>8}",
+            "módulo",
+            format!("{} b", self.phi.bits()),
+            format!("{} b", self.lambda.bits())
+        )?;
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>8}",
+            "d",
+            format!("{} b", self.d_phi.bits()),
+            format!("{} b", self.d_lambda.bits())
+        )?;
+        writeln!(f, "d (φ) = {}", self.d_phi)?;
+        write!(f, "d (λ) = {}", self.d_lambda)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::RandBigInt;
+    use num_traits::Zero;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mersenne(expoente: u32) -> BigInt {
+        (BigInt::one() << expoente) - 1
+    }
+
+    /// Chave com p = 2^89 − 1 e q = 2^61 − 1, ambos coprimos com 65537 − 1.
+    fn chave_fixa() -> RsaPrivateKey {
+        RsaPrivateKey::a_partir_dos_primos(mersenne(89), mersenne(61), BigInt::from(65537)).unwrap()
+    }
+
+    #[test]
+    fn debug_da_chave_privada_nao_expoe_segredos() {
+        let chave = chave_fixa();
+        let texto = format!("{:?}", chave);
+        assert!(texto.contains(&chave.n().to_string()));
+        for segredo in [
+            chave.d(),
+            chave.p(),
+            chave.q(),
+            chave.dp(),
+            chave.dq(),
+            chave.qinv(),
+        ] {
+            assert!(!texto.contains(&segredo.to_string()), "{}", texto);
+        }
+    }
+
+    #[test]
+    fn crt_concorda_com_a_exponenciacao_direta() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let opcoes_euler = OpcoesGeracao {
+            totiente: Totiente::Euler,
+            ..OpcoesGeracao::default()
+        };
+        let chaves = [
+            chave_fixa(),
+            RsaPrivateKey::a_partir_dos_primos_com_opcoes(
+                mersenne(89),
+                mersenne(61),
+                BigInt::from(65537),
+                &opcoes_euler,
+            )
+            .unwrap(),
+            KeyPair::generate_com_rng(
+                512,
+                &BigInt::from(65537),
+                &OpcoesGeracao::default(),
+                &mut rng,
+            )
+            .unwrap()
+            .privada,
+        ];
+        for chave in &chaves {
+            let publica = chave.chave_publica();
+            let zero = BigInt::zero();
+            for c in [zero.clone(), BigInt::one(), chave.n() - 1]
+                .into_iter()
+                .chain((0..20).map(|_| rng.gen_bigint_range(&zero, chave.n())))
+            {
+                let m = chave.aplicar_expoente_privado(&c).unwrap();
+                assert_eq!(m, chave.aplicar_expoente_privado_sem_crt(&c).unwrap());
+                assert_eq!(publica.aplicar_expoente_publico(&m).unwrap(), c);
+            }
+            assert_eq!(
+                chave.aplicar_expoente_privado(chave.n()),
+                Err(RsaError::InvalidBlock)
+            );
+            assert_eq!(
+                chave.aplicar_expoente_privado(&BigInt::from(-1)),
+                Err(RsaError::InvalidBlock)
+            );
+        }
+    }
+
+    #[test]
+    fn cegamento_reusado_alem_do_limite_mantem_o_resultado() {
+        let mut rng = StdRng::seed_from_u64(16);
+        let chave = chave_fixa();
+        let copia = chave.clone();
+        let zero = BigInt::zero();
+        for _ in 0..3 * crate::blinding::LIMITE_USOS_CEGAMENTO + 1 {
+            let c = rng.gen_bigint_range(&zero, chave.n());
+            let esperado = chave.aplicar_expoente_privado_sem_crt(&c).unwrap();
+            assert_eq!(
+                chave.aplicar_expoente_privado_com_rng(&c, &mut rng),
+                Ok(esperado.clone())
+            );
+            assert_eq!(chave.aplicar_expoente_privado(&c), Ok(esperado.clone()));
+            assert_eq!(copia.aplicar_expoente_privado(&c), Ok(esperado));
+        }
+        assert_eq!(chave.clone(), chave);
+    }
+
+    #[test]
+    fn d_e_calculado_modulo_lambda_por_padrao() {
+        // Com e = 65539 os inversos módulo φ(n) e λ(n) diferem, então o teste distingue os dois
+        let (p, q, e) = (mersenne(89), mersenne(61), BigInt::from(65539));
+        let lambda = funcao_carmichael(&p, &q);
+        let phi = funcao_totiente(&p, &q);
+        assert_eq!(&phi % &lambda, BigInt::zero());
+        assert!(lambda < phi);
+
+        let chave = RsaPrivateKey::a_partir_dos_primos(p.clone(), q.clone(), e.clone()).unwrap();
+        assert_eq!(*chave.d(), inverso_modular(&e, &lambda).unwrap());
+
+        let opcoes_euler = OpcoesGeracao {
+            totiente: Totiente::Euler,
+            ..OpcoesGeracao::default()
+        };
+        let euler =
+            RsaPrivateKey::a_partir_dos_primos_com_opcoes(p, q, e.clone(), &opcoes_euler).unwrap();
+        assert_eq!(*euler.d(), inverso_modular(&e, &phi).unwrap());
+        assert_ne!(euler.d(), chave.d());
+        assert_eq!(euler.d() % &lambda, *chave.d());
+    }
+
+    #[test]
+    fn d_pequeno_demais_e_rejeitado() {
+        // nlen = 150: d precisa ser maior que 2^75. Escolhe d e deriva e = d⁻¹ mod λ(n).
+        let (p, q) = (mersenne(89), mersenne(61));
+        let lambda = funcao_carmichael(&p, &q);
+        let limite = BigInt::one() << 75;
+        let coprimo = |mut d: BigInt, passo: i32| {
+            while !d.gcd(&lambda).is_one() {
+                d += passo;
+            }
+            d
+        };
+
+        let d_abaixo = coprimo(&limite - 1, -2);
+        let e = inverso_modular(&d_abaixo, &lambda).unwrap();
+        assert_eq!(
+            RsaPrivateKey::a_partir_dos_primos(p.clone(), q.clone(), e),
+            Err(RsaError::InvalidKey)
+        );
+
+        let d_acima = coprimo(&limite + 1, 2);
+        let e = inverso_modular(&d_acima, &lambda).unwrap();
+        let chave = RsaPrivateKey::a_partir_dos_primos(p, q, e).unwrap();
+        assert_eq!(*chave.d(), d_acima);
+    }
+
+    #[test]
+    fn expoente_publico_invalido_e_rejeitado_na_leitura() {
+        assert_eq!("3".parse(), Ok(ExpoentePublico::Tres));
+        assert_eq!(" 17 ".parse(), Ok(ExpoentePublico::Dezessete));
+        assert_eq!("65537".parse(), Ok(ExpoentePublico::F4));
+        assert_eq!(
+            "65539".parse(),
+            Ok(ExpoentePublico::Personalizado(BigInt::from(65539)))
+        );
+        for texto in ["1", "0", "4", "65536", "-3", "-1", "3.0", "", "e"] {
+            assert_eq!(
+                texto.parse::<ExpoentePublico>(),
+                Err(RsaError::InvalidKey),
+                "{:?}",
+                texto
+            );
+        }
+    }
+
+    #[test]
+    fn modo_estrito_exige_expoente_entre_2_16_e_2_256() {
+        let limite: BigInt = BigInt::one() << 256;
+        for e in [BigInt::from(3), BigInt::from(17), BigInt::from(65535)] {
+            assert_eq!(validar_expoente_publico(&e, false), Ok(()));
+            assert_eq!(
+                validar_expoente_publico(&e, true),
+                Err(RsaError::InvalidKey)
+            );
+        }
+        for e in [BigInt::from(65537), &limite - 1] {
+            assert_eq!(validar_expoente_publico(&e, true), Ok(()));
+        }
+        for e in [&limite + 1, BigInt::from(65538)] {
+            assert_eq!(
+                validar_expoente_publico(&e, true),
+                Err(RsaError::InvalidKey)
+            );
+        }
+        for e in [-3, 1, 2, 4] {
+            assert_eq!(
+                validar_expoente_publico(&BigInt::from(e), false),
+                Err(RsaError::InvalidKey)
+            );
+        }
+
+        let estrito = OpcoesGeracao {
+            modo_estrito: true,
+            ..OpcoesGeracao::default()
+        };
+        assert_eq!(
+            KeyPair::generate_com_opcoes(2048, &BigInt::from(17), &estrito).map(|_| ()),
+            Err(RsaError::InvalidKey)
+        );
+    }
+
+    #[test]
+    fn expoente_com_fator_comum_com_p_menos_1_e_rejeitado() {
+        // 3 divide 2^88 − 1, logo divide p − 1 = 2^89 − 2 (e também φ e λ)
+        let (p, q, tres) = (mersenne(89), mersenne(61), BigInt::from(3));
+        assert_eq!(
+            validar_expoente(&tres, &p, &q),
+            Err(RsaError::NotInvertible)
+        );
+        assert_eq!(validar_expoente(&BigInt::from(65537), &p, &q), Ok(()));
+        for totiente in [Totiente::Euler, Totiente::Carmichael] {
+            let opcoes = OpcoesGeracao {
+                totiente,
+                ..OpcoesGeracao::default()
+            };
+            assert_eq!(
+                RsaPrivateKey::a_partir_dos_primos_com_opcoes(
+                    p.clone(),
+                    q.clone(),
+                    tres.clone(),
+                    &opcoes
+                ),
+                Err(RsaError::NotInvertible)
+            );
+        }
+    }
+
+    #[test]
+    fn primo_com_fator_comum_com_e_e_sorteado_de_novo() {
+        // Cerca de metade dos primos tem p ≡ 1 (mod 3); o rng clonado mostra
+        // qual seria o primeiro primo sorteado sem a exigência de gcd(e, p − 1) = 1
+        let tres = BigInt::from(3);
+        let teste = TestePrimalidade::default();
+        let mut sorteados_de_novo = 0;
+        for semente in 0..16 {
+            let mut rng = StdRng::seed_from_u64(semente);
+            let primeiro = TipoPrimo::Aleatorio
+                .gerar_com_teste(128, teste, &mut rng.clone())
+                .unwrap();
+            let p = gerar_primo_coprimo(128, &tres, TipoPrimo::Aleatorio, teste, &mut rng).unwrap();
+            assert!(mdc_binario(&tres, &(&p - 1)).is_one());
+            assert_eq!(p.bits(), 128);
+            if p != primeiro {
+                assert!(!mdc_binario(&tres, &(&primeiro - 1)).is_one());
+                sorteados_de_novo += 1;
+            }
+        }
+        assert!(sorteados_de_novo > 0);
+
+        let mut rng = StdRng::seed_from_u64(19);
+        let chaves =
+            KeyPair::generate_com_rng(512, &tres, &OpcoesGeracao::default(), &mut rng).unwrap();
+        let privada = &chaves.privada;
+        assert_eq!(validar_expoente(&tres, privada.p(), privada.q()), Ok(()));
+    }
+
+    #[test]
+    fn comparacao_didatica_usa_todos_os_fatores_primos() {
+        let (p, q, r, e) = (
+            mersenne(89),
+            mersenne(61),
+            mersenne(107),
+            BigInt::from(65537),
+        );
+        let primos = [p.clone(), q.clone(), r.clone()];
+        let comparacao = ComparacaoExpoentes::calcular_primos(&primos, &e).unwrap();
+        let n: BigInt = primos.iter().product();
+
+        let (p_1, q_1, r_1): (BigInt, BigInt, BigInt) = (&p - 1, &q - 1, &r - 1);
+        assert_eq!(comparacao.phi, &p_1 * &q_1 * &r_1);
+        assert_eq!(comparacao.lambda, p_1.lcm(&q_1).lcm(&r_1));
+        assert_eq!(comparacao.phi.bits(), n.bits());
+        assert!((&e * &comparacao.d_lambda % &comparacao.lambda).is_one());
+        assert!((&e * &comparacao.d_phi % &comparacao.phi).is_one());
+
+        assert_eq!(
+            ComparacaoExpoentes::calcular(&p, &q, &e),
+            ComparacaoExpoentes::calcular_primos(&primos[..2], &e)
+        );
+    }
+
+    #[test]
+    fn chave_com_tres_primos_usa_o_crt_generalizado() {
+        let mut rng = StdRng::seed_from_u64(25);
+        let e = BigInt::from(65537);
+        let opcoes = OpcoesGeracao {
+            quantidade_primos: 3,
+            ..OpcoesGeracao::default()
+        };
+        let chaves = KeyPair::generate_com_rng(1024, &e, &opcoes, &mut rng).unwrap();
+        let chave = &chaves.privada;
+        assert_eq!(chave.quantidade_primos(), 3);
+        assert_eq!(chave.outros_primos().len(), 1);
+        assert_eq!(chave.n().bits(), 1024);
+        assert_eq!(chaves.publica.n, *chave.n());
+
+        let mut primos = vec![chave.p().clone(), chave.q().clone()];
+        primos.extend(chave.outros_primos().iter().map(|outro| outro.r().clone()));
+        assert_eq!(primos.iter().product::<BigInt>(), *chave.n());
+        assert_eq!(
+            *chave.d(),
+            inverso_modular(&e, &Totiente::Carmichael.calcular_primos(&primos)).unwrap()
+        );
+
+        // t_i = (r_1 · … · r_(i−1))⁻¹ mod r_i e d_i = d mod (r_i − 1)
+        for (i, outro) in chave.outros_primos().iter().enumerate() {
+            let anteriores: BigInt = primos[..i + 2].iter().product();
+            assert_eq!(*outro.t(), inverso_modular(&anteriores, outro.r()).unwrap());
+            assert!((&anteriores * outro.t() % outro.r()).is_one());
+            assert_eq!(*outro.d(), chave.d() % (outro.r() - 1));
+        }
+
+        let zero = BigInt::zero();
+        for c in [zero.clone(), BigInt::one(), chave.n() - 1]
+            .into_iter()
+            .chain((0..10).map(|_| rng.gen_bigint_range(&zero, chave.n())))
+        {
+            let m = chave.aplicar_expoente_privado(&c).unwrap();
+            assert_eq!(m, chave.aplicar_expoente_privado_sem_crt(&c).unwrap());
+            assert_eq!(chaves.publica.aplicar_expoente_publico(&m).unwrap(), c);
+        }
+
+        // A mesma chave montada a partir dos primos
+        let montada = RsaPrivateKey::a_partir_de_varios_primos(primos.clone(), e.clone()).unwrap();
+        assert_eq!(montada, *chave);
+
+        let repetidos = vec![primos[0].clone(), primos[1].clone(), primos[0].clone()];
+        assert_eq!(
+            RsaPrivateKey::a_partir_de_varios_primos(repetidos, e.clone()),
+            Err(RsaError::InvalidKey)
+        );
+        assert_eq!(
+            RsaPrivateKey::a_partir_de_varios_primos(primos[..1].to_vec(), e),
+            Err(RsaError::InvalidKey)
+        );
+    }
+
+    #[test]
+    fn quantidade_de_primos_fora_do_limite_e_rejeitada() {
+        for (bits, maximo) in [
+            (512, 2),
+            (1023, 2),
+            (1024, 3),
+            (4095, 3),
+            (4096, 4),
+            (8192, QUANTIDADE_MAXIMA_PRIMOS),
+        ] {
+            assert_eq!(quantidade_maxima_primos(bits), maximo, "{}", bits);
+        }
+
+        let mut rng = StdRng::seed_from_u64(25);
+        let e = BigInt::from(65537);
+        for (bits, quantidade_primos) in [(1023, 3), (1024, 4), (4096, 5), (1024, 1), (1024, 0)] {
+            let opcoes = OpcoesGeracao {
+                quantidade_primos,
+                ..OpcoesGeracao::default()
+            };
+            assert_eq!(
+                KeyPair::generate_com_rng(bits, &e, &opcoes, &mut rng).map(|_| ()),
+                Err(RsaError::InvalidKey),
+                "{} bits, {} primos",
+                bits,
+                quantidade_primos
+            );
+        }
+
+        let estrito = OpcoesGeracao {
+            modo_estrito: true,
+            quantidade_primos: 3,
+            ..OpcoesGeracao::default()
+        };
+        assert_eq!(
+            KeyPair::generate_com_rng(2048, &e, &estrito, &mut rng).map(|_| ()),
+            Err(RsaError::InvalidKey)
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Implementação educacional do algoritmo RSA sobre `BigInt`.
+//!
+//! O crate é dividido nas etapas clássicas do RSA: aritmética modular,
+//! geração de primos, geração de chaves, conversão de mensagens em blocos
+//! numéricos, esquemas de preenchimento (*padding*) e assinaturas digitais,
+//! além das funções de hash usadas por esses esquemas e das fontes de
+//! aleatoriedade que os alimentam.
+
+pub mod arithmetic;
+pub mod blinding;
+pub mod encoding;
+pub mod error;
+pub mod fips;
+pub mod hash;
+pub mod keys;
+pub mod padding;
+pub mod primes;
+pub mod random;
+pub mod signature;
@@ -1,135 +1,163 @@
-use num_bigint::BigInt;
-use num_traits::{Zero, One};
-use std::ops::Shr;
-
-// --------------------------------------------------------
-// 1. Definição das Funções Matemáticas Essenciais (BigInt)
-// --------------------------------------------------------
-
-fn algoritmo_euclidiano_estendido(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
-    if *a == BigInt::from(0) {
-        return (b.clone(), BigInt::from(0), BigInt::from(1));
-    }
-
-    let (gcd, x1, y1) = algoritmo_euclidiano_estendido(&(b % a), a);
-    let x = &y1 - (b / a) * &x1;
-    let y = x1;
-
-    (gcd, x, y)
+use rand::SeedableRng;
+use rsa_simulado::encoding::{numeros_para_string, string_para_numeros};
+use rsa_simulado::error::RsaError;
+use rsa_simulado::fips;
+use rsa_simulado::hash::Sha256;
+use rsa_simulado::keys::{ComparacaoExpoentes, ExpoentePublico, KeyPair, OpcoesGeracao};
+use rsa_simulado::padding::{
+    criptografar_sem_padding, descriptografar_sem_padding, oaep, pkcs1v15,
+};
+use rsa_simulado::primes::TestePrimalidade;
+use rsa_simulado::random::{EntropiaSistema, HmacDrbg, RandomSource};
+use rsa_simulado::signature::pss;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
-fn inverso_modular(e: &BigInt, phi_n: &BigInt) -> BigInt {
-    let (_, mut x, _) = algoritmo_euclidiano_estendido(e, phi_n);
+fn main() -> Result<(), RsaError> {
+    println!("--- Algoritmo RSA Simulado (Propósito Educacional) ---");
 
-    if x < BigInt::from(0) {
-        x += phi_n;
-    }
-    x
-}
+    let bits = 1024;
 
-fn exponenciacao_modular(base: &BigInt, exp: &BigInt, modulo: &BigInt) -> BigInt {
-    let mut resultado = BigInt::from(1);
-    let mut base = base % modulo;
-    let mut exp = exp.clone();
+    println!("\n[9] Geração de Chaves:");
 
-    while exp > BigInt::from(0) {
-        if &exp % BigInt::from(2) == BigInt::from(1) {
-            resultado = (resultado * &base) % modulo;
+    // Expoente público escolhido com `--e=<valor>` (3, 17, 65537 ou outro ímpar)
+    let expoente = match std::env::args()
+        .find_map(|argumento| argumento.strip_prefix("--e=").map(String::from))
+    {
+        Some(valor) => valor.parse::<ExpoentePublico>()?,
+        None => ExpoentePublico::default(),
+    };
+    // Semente fixa com `--semente=<n>`: HMAC-DRBG reproduz chaves e textos cifrados
+    let mut fonte: Box<dyn RandomSource> = match std::env::args()
+        .find_map(|argumento| argumento.strip_prefix("--semente=").map(String::from))
+    {
+        Some(valor) => {
+            let semente = valor
+                .parse::<u64>()
+                .map_err(|_| RsaError::RandomnessError)?;
+            Box::new(HmacDrbg::<Sha256>::seed_from_u64(semente))
         }
-        exp = exp.shr(1);
-        base = (&base * &base) % modulo;
+        None => Box::new(EntropiaSistema),
+    };
+    // Quantidade de fatores primos de n com `--primos=<k>` (RSA multiprimo para k > 2)
+    let mut opcoes = OpcoesGeracao::default();
+    if let Some(valor) =
+        std::env::args().find_map(|argumento| argumento.strip_prefix("--primos=").map(String::from))
+    {
+        opcoes.quantidade_primos = valor.parse().map_err(|_| RsaError::InvalidKey)?;
     }
-    resultado
-}
-
-fn eh_primo(n: &BigInt, _k: u32) -> bool {
-    if n == &BigInt::from(61) || n == &BigInt::from(53) {
-        return true;
+    // Primos aprovados pelo Baillie–PSW em vez do Miller–Rabin com `--bpsw`
+    if std::env::args().any(|argumento| argumento == "--bpsw") {
+        opcoes.teste = TestePrimalidade::BailliePsw;
     }
-    false
-}
-
-fn gerar_primo(bits: u32) -> BigInt {
-    if bits == 256 {
-        return BigInt::from(61);
+    let chaves = KeyPair::generate_com_rng(bits, &expoente.valor(), &opcoes, &mut *fonte)?;
+
+    println!("  > Módulo n (Público): {}", chaves.publica.n);
+    println!("  > Expoente Público e (Público): {}", chaves.publica.e);
+    println!("  > Chave Privada (Secreta): {:?}", chaves.privada);
+    println!(
+        "  > Fatores Primos de n: {}",
+        chaves.privada.quantidade_primos()
+    );
+
+    // Modo didático (`cargo run -- --didatico`): expõe d calculado com φ(n) e λ(n)
+    if std::env::args().any(|argumento| argumento == "--didatico") {
+        println!("\n[10] Expoente Privado: φ(n) x λ(n):");
+        let privada = &chaves.privada;
+        let primos: Vec<_> = [privada.p(), privada.q()]
+            .into_iter()
+            .chain(privada.outros_primos().iter().map(|outro| outro.r()))
+            .cloned()
+            .collect();
+        let comparacao = ComparacaoExpoentes::calcular_primos(&primos, privada.e())?;
+        for linha in comparacao.to_string().lines() {
+            println!("  > {}", linha);
+        }
     }
-    BigInt::from(53)
-}
-
-// --------------------------------------------------------
-// 2. Funções de Conversão Mensagem ↔ Números
-// --------------------------------------------------------
-
-fn string_para_numeros(texto: &str) -> Vec<BigInt> {
-    texto
-        .bytes()
-        .map(|byte| BigInt::from(byte as u32))
-        .collect()
-}
-
-fn numeros_para_string(numeros: &[BigInt]) -> String {
-    numeros
-        .iter()
-        .map(|num| {
-            let byte = num.to_string().parse::<u8>().unwrap_or(0);
-            byte as char
-        })
-        .collect()
-}
-
-// --------------------------------------------------------
-// 3. Função Principal (main)
-// --------------------------------------------------------
-
-fn main() {
-    println!("--- Algoritmo RSA Simulado (Propósito Educacional) ---");
-
-    let bits = 512;
-
-    println!("\n[9] Geração de Chaves:");
-
-    let p = gerar_primo(bits / 2);
-    let q = gerar_primo(bits / 2);
-    println!("  > Primo p: {}", p);
-    println!("  > Primo q: {}", q);
-
-    let n = &p * &q;
-    println!("  > Módulo n (Público): {}", n);
-
-    let phi_n = (&p - 1) * (&q - 1);
-    println!("  > Phi(n) (Secreto): {}", phi_n);
-
-    let e = BigInt::from(65537);
-    println!("  > Expoente Público e (Público): {}", e);
-
-    let d = inverso_modular(&e, &phi_n);
-    println!("  > Expoente Privado d (Secreto): {}", d);
 
     let mensagem_str = "Ola!";
+    // Modo FIPS (`cargo run -- --fips`): gera uma chave pelo FIPS 186-5 e relata cada requisito
+    if std::env::args().any(|argumento| argumento == "--fips") {
+        println!("\n[10] Geração Conforme o FIPS 186-5:");
+        let (_, relatorio) =
+            fips::gerar_chaves_com_rng(2048, &ExpoentePublico::F4.valor(), &mut *fonte)?;
+        for linha in relatorio.to_string().lines() {
+            println!("  > {}", linha);
+        }
+    }
+
     println!("\n[11] Criptografia:");
     println!("  > Mensagem Original: '{}'", mensagem_str);
 
-    let blocos_mensagem = string_para_numeros(mensagem_str);
-    let mut texto_criptografado: Vec<BigInt> = Vec::new();
-
-    for m in &blocos_mensagem {
-        let c = exponenciacao_modular(m, &e, &n);
-        texto_criptografado.push(c);
-    }
+    let blocos_mensagem = string_para_numeros(mensagem_str, &chaves.publica.n)?;
+    let texto_criptografado = criptografar_sem_padding(&blocos_mensagem, &chaves.publica)?;
 
-    println!("  > Texto Criptografado (Blocos): {:?}", texto_criptografado);
+    println!(
+        "  > Texto Criptografado (Blocos): {:?}",
+        texto_criptografado
+    );
 
     println!("\n[12] Descriptografia:");
 
-    let mut blocos_descriptografados: Vec<BigInt> = Vec::new();
-
-    for c in &texto_criptografado {
-        let m_original = exponenciacao_modular(c, &d, &n);
-        blocos_descriptografados.push(m_original);
-    }
+    let blocos_descriptografados =
+        descriptografar_sem_padding(&texto_criptografado, &chaves.privada)?;
+    let mensagem_descriptografada =
+        numeros_para_string(&blocos_descriptografados, chaves.privada.n())?;
 
-    let mensagem_descriptografada = numeros_para_string(&blocos_descriptografados);
-
-    println!("  > Mensagem Descriptografada (Blocos): {:?}", blocos_descriptografados);
+    println!(
+        "  > Mensagem Descriptografada (Blocos): {:?}",
+        blocos_descriptografados
+    );
     println!("  > Resultado Final: '{}'", mensagem_descriptografada);
+
+    println!("\n[13] Preenchimento PKCS#1 v1.5:");
+
+    let cifrado =
+        pkcs1v15::criptografar_com_rng(&chaves.publica, mensagem_str.as_bytes(), &mut *fonte)?;
+    println!("  > Texto Criptografado: {}", hex(&cifrado));
+
+    let decifrado = pkcs1v15::descriptografar_rejeicao_implicita_com_rng(
+        &chaves.privada,
+        &cifrado,
+        &mut *fonte,
+    )?;
+    println!(
+        "  > Resultado Final: '{}'",
+        String::from_utf8_lossy(&decifrado)
+    );
+
+    println!("\n[14] Preenchimento OAEP (SHA-256):");
+
+    let cifrado = oaep::criptografar_com_rng::<Sha256, _>(
+        &chaves.publica,
+        mensagem_str.as_bytes(),
+        None,
+        &mut *fonte,
+    )?;
+    println!("  > Texto Criptografado: {}", hex(&cifrado));
+
+    let decifrado =
+        oaep::descriptografar_com_rng::<Sha256, _>(&chaves.privada, &cifrado, None, &mut *fonte)?;
+    println!(
+        "  > Resultado Final: '{}'",
+        String::from_utf8_lossy(&decifrado)
+    );
+
+    println!("\n[15] Assinatura RSASSA-PSS (SHA-256):");
+
+    let assinatura = pss::assinar_com_rng::<Sha256, _>(
+        &chaves.privada,
+        mensagem_str.as_bytes(),
+        32,
+        &mut *fonte,
+    )?;
+    println!("  > Assinatura: {}", hex(&assinatura));
+
+    let verificacao =
+        pss::verificar::<Sha256>(&chaves.publica, mensagem_str.as_bytes(), &assinatura, 32);
+    println!("  > Assinatura Válida: {}", verificacao.is_ok());
+
+    Ok(())
 }
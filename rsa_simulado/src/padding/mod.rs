@@ -0,0 +1,76 @@
+// --------------------------------------------------------
+// Esquemas de Preenchimento (Padding)
+// --------------------------------------------------------
+
+pub mod oaep;
+pub mod pkcs1v15;
+
+use crate::encoding::{inteiro_para_octetos, octetos_para_inteiro, tamanho_em_bytes};
+use crate::error::RsaError;
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+use num_bigint::BigInt;
+
+/// RSA "de livro-texto", sem preenchimento: c = m^e mod n para cada bloco.
+/// É determinístico e maleável, servindo apenas para fins didáticos.
+pub fn criptografar_sem_padding(
+    blocos: &[BigInt],
+    chave: &RsaPublicKey,
+) -> Result<Vec<BigInt>, RsaError> {
+    blocos
+        .iter()
+        .map(|m| chave.aplicar_expoente_publico(m))
+        .collect()
+}
+
+/// Inverso de `criptografar_sem_padding`: m = c^d mod n para cada bloco, via CRT.
+pub fn descriptografar_sem_padding(
+    blocos: &[BigInt],
+    chave: &RsaPrivateKey,
+) -> Result<Vec<BigInt>, RsaError> {
+    blocos
+        .iter()
+        .map(|c| chave.aplicar_expoente_privado(c))
+        .collect()
+}
+
+/// RSAEP sobre octetos: cifra a mensagem codificada `em` (k bytes) e devolve
+/// o texto cifrado também com k bytes.
+pub(crate) fn cifrar_octetos(chave: &RsaPublicKey, em: &[u8]) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(&chave.n);
+    let m = octetos_para_inteiro(em);
+    let c = chave.aplicar_expoente_publico(&m)?;
+    inteiro_para_octetos(&c, k)
+}
+
+/// RSADP sobre octetos: decifra `cifrado`, que precisa ter exatamente k bytes.
+/// O cegamento, quando precisa de um novo r, o sorteia a partir de `rng`.
+pub(crate) fn decifrar_octetos<R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(chave.n());
+    if cifrado.len() != k {
+        return Err(RsaError::InvalidBlock);
+    }
+    let c = octetos_para_inteiro(cifrado);
+    let m = chave.aplicar_expoente_privado_com_rng(&c, rng)?;
+    inteiro_para_octetos(&m, k)
+}
+
+/// Máscara com todos os bits ligados se `a == b`, ou zero caso contrário.
+fn mascara_igual(a: u8, b: u8) -> usize {
+    let diferenca = usize::from(a ^ b);
+    (diferenca.wrapping_sub(1) >> (usize::BITS - 1)).wrapping_neg()
+}
+
+/// Máscara com todos os bits ligados se `a < b` (para valores menores que 2^63).
+fn mascara_menor(a: usize, b: usize) -> usize {
+    (a.wrapping_sub(b) >> (usize::BITS - 1)).wrapping_neg()
+}
+
+/// Devolve `a` se `mascara` tiver todos os bits ligados e `b` se for zero.
+fn selecionar(mascara: usize, a: usize, b: usize) -> usize {
+    (a & mascara) | (b & !mascara)
+}
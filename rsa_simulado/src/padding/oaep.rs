@@ -0,0 +1,270 @@
+// --------------------------------------------------------
+// RSAES-OAEP (RFC 8017, seção 7.1)
+// --------------------------------------------------------
+
+use super::{cifrar_octetos, decifrar_octetos, mascara_igual, selecionar};
+use crate::encoding::tamanho_em_bytes;
+use crate::error::RsaError;
+use crate::hash::{mgf1, FuncaoHash};
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+use rand::Rng;
+
+/// Maior mensagem que cabe em um bloco OAEP de `k` bytes com a função de hash `H`.
+pub fn tamanho_maximo_mensagem<H: FuncaoHash>(k: usize) -> usize {
+    k.saturating_sub(2 * H::TAMANHO_SAIDA + 2)
+}
+
+/// Cifra `mensagem` com RSAES-OAEP, usando `H` tanto como hash do rótulo
+/// quanto na MGF1. Sem rótulo, é usada a cadeia vazia.
+pub fn criptografar<H: FuncaoHash>(
+    chave: &RsaPublicKey,
+    mensagem: &[u8],
+    rotulo: Option<&[u8]>,
+) -> Result<Vec<u8>, RsaError> {
+    criptografar_com_rng::<H, _>(chave, mensagem, rotulo, &mut rand::thread_rng())
+}
+
+/// Variante de `criptografar` que sorteia a semente a partir de `rng`.
+pub fn criptografar_com_rng<H: FuncaoHash, R: RandomSource + ?Sized>(
+    chave: &RsaPublicKey,
+    mensagem: &[u8],
+    rotulo: Option<&[u8]>,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(&chave.n);
+    let em = codificar::<H, R>(mensagem, k, rotulo, rng)?;
+    cifrar_octetos(chave, &em)
+}
+
+/// EME-OAEP: EM = 0x00 || maskedSeed || maskedDB, com
+/// DB = H(L) || PS || 0x01 || M.
+///
+/// Retorna `RsaError::MessageTooLong` se `mensagem` tiver mais de k − 2hLen − 2 bytes.
+pub fn codificar<H: FuncaoHash, R: RandomSource + ?Sized>(
+    mensagem: &[u8],
+    k: usize,
+    rotulo: Option<&[u8]>,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let h_len = H::TAMANHO_SAIDA;
+    if k < 2 * h_len + 2 || mensagem.len() > tamanho_maximo_mensagem::<H>(k) {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    let mut db = H::digest(rotulo.unwrap_or_default());
+    db.resize(k - mensagem.len() - h_len - 2, 0x00);
+    db.push(0x01);
+    db.extend_from_slice(mensagem);
+
+    let mut semente = vec![0u8; h_len];
+    rng.fill(semente.as_mut_slice());
+
+    aplicar_mascara(&mut db, &mgf1::<H>(&semente, k - h_len - 1));
+    aplicar_mascara(&mut semente, &mgf1::<H>(&db, h_len));
+
+    let mut em = Vec::with_capacity(k);
+    em.push(0x00);
+    em.extend_from_slice(&semente);
+    em.extend_from_slice(&db);
+    Ok(em)
+}
+
+/// Decifra um texto produzido por `criptografar` com o mesmo `H` e rótulo.
+pub fn descriptografar<H: FuncaoHash>(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+    rotulo: Option<&[u8]>,
+) -> Result<Vec<u8>, RsaError> {
+    descriptografar_com_rng::<H, _>(chave, cifrado, rotulo, &mut rand::thread_rng())
+}
+
+/// Variante de `descriptografar` que sorteia o cegamento a partir de `rng`.
+pub fn descriptografar_com_rng<H: FuncaoHash, R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+    rotulo: Option<&[u8]>,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(chave.n());
+    if k < 2 * H::TAMANHO_SAIDA + 2 {
+        return Err(RsaError::DecodingError);
+    }
+    let em = decifrar_octetos(chave, cifrado, rng)?;
+    decodificar::<H>(&em, rotulo)
+}
+
+/// Inverso de `codificar`.
+///
+/// Todas as verificações (byte inicial, H(L) e separador 0x01) são feitas sem
+/// desvios dependentes dos dados e resultam no mesmo `RsaError::DecodingError`,
+/// para não criar o oráculo do ataque de Manger.
+pub fn decodificar<H: FuncaoHash>(em: &[u8], rotulo: Option<&[u8]>) -> Result<Vec<u8>, RsaError> {
+    let h_len = H::TAMANHO_SAIDA;
+    if em.len() < 2 * h_len + 2 {
+        return Err(RsaError::DecodingError);
+    }
+
+    let (y, resto) = em.split_at(1);
+    let (semente_mascarada, db_mascarado) = resto.split_at(h_len);
+
+    let mut semente = semente_mascarada.to_vec();
+    aplicar_mascara(&mut semente, &mgf1::<H>(db_mascarado, h_len));
+    let mut db = db_mascarado.to_vec();
+    aplicar_mascara(&mut db, &mgf1::<H>(&semente, db_mascarado.len()));
+
+    let hash_rotulo = H::digest(rotulo.unwrap_or_default());
+    let mut valido = mascara_igual(y[0], 0x00);
+    for (&esperado, &obtido) in hash_rotulo.iter().zip(&db) {
+        valido &= mascara_igual(esperado, obtido);
+    }
+
+    // Após H(L) só podem vir zeros até o primeiro 0x01
+    let mut achou_um = 0usize;
+    let mut separador = 0usize;
+    for (i, &byte) in db.iter().enumerate().skip(h_len) {
+        let eh_um = mascara_igual(byte, 0x01);
+        let eh_zero = mascara_igual(byte, 0x00);
+        separador = selecionar(eh_um & !achou_um, i, separador);
+        valido &= achou_um | eh_zero | eh_um;
+        achou_um |= eh_um;
+    }
+    valido &= achou_um;
+
+    if valido == 0 {
+        return Err(RsaError::DecodingError);
+    }
+    Ok(db[separador + 1..].to_vec())
+}
+
+fn aplicar_mascara(dados: &mut [u8], mascara: &[u8]) {
+    for (byte, m) in dados.iter_mut().zip(mascara) {
+        *byte ^= m;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{chave_de_primos_hex, chave_de_teste_1024, hex_para_octetos};
+    use crate::hash::{Sha1, Sha256};
+    use crate::random::{FonteFixa, HmacDrbg};
+    use rand::SeedableRng;
+
+    // Chave, mensagem e semente do exemplo oaep-int.txt do PKCS #1 v2.1,
+    // reproduzido nos vetores da RFC 8017.
+    const P: &str = "eecfae81b1b9b3c908810b10a1b5600199eb9f44aef4fda493b81a9e3d84f632\
+                     124ef0236e5d1e3b7e28fae7aa040a2d5b252176459d1f397541ba2a58fb6599";
+    const Q: &str = "c97fb1f027f453f6341233eaaad1d9353f6c42d08866b1d05a0f2035028b9d86\
+                     9840b41666b42e92ea0da3b43204b5cfce3352524d0416a5a441e700af461503";
+    const MENSAGEM: &str = "d436e99569fd32a7c8a05bbc90d32c49";
+    const SEMENTE: &str = "aafd12f659cae63489b479e5076ddec2f06cb58f";
+
+    fn chave() -> RsaPrivateKey {
+        chave_de_primos_hex(P, Q, 0x11)
+    }
+
+    /// Codifica e cifra `MENSAGEM` com a `semente` fixa, confere EM e C e
+    /// decifra de volta.
+    fn conferir_vetor<H: FuncaoHash>(semente: &str, em_esperado: &str, cifrado_esperado: &str) {
+        let chave = chave();
+        let mensagem = hex_para_octetos(MENSAGEM);
+        let semente = hex_para_octetos(semente);
+        let k = tamanho_em_bytes(chave.n());
+
+        let em = codificar::<H, _>(&mensagem, k, None, &mut FonteFixa::new(&semente)).unwrap();
+        assert_eq!(em, hex_para_octetos(em_esperado));
+
+        let cifrado = criptografar_com_rng::<H, _>(
+            &chave.chave_publica(),
+            &mensagem,
+            None,
+            &mut FonteFixa::new(&semente),
+        )
+        .unwrap();
+        assert_eq!(cifrado, hex_para_octetos(cifrado_esperado));
+        assert_eq!(
+            descriptografar::<H>(&chave, &cifrado, None).unwrap(),
+            mensagem
+        );
+    }
+
+    #[test]
+    fn vetor_da_rfc_8017_com_sha1() {
+        conferir_vetor::<Sha1>(
+            SEMENTE,
+            "00eb7a19ace9e3006350e329504b45e2ca82310b26dcd87d5c68f1eea8f55267\
+             c31b2e8bb4251f84d7e0b2c04626f5aff93edcfb25c9c2b3ff8ae10e839a2ddb\
+             4cdcfe4ff47728b4a1b7c1362baad29ab48d2869d5024121435811591be392f9\
+             82fb3e87d095aeb40448db972f3ac14f7bc275195281ce32d2f1b76d4d353e2d",
+            "1253e04dc0a5397bb44a7ab87e9bf2a039a33d1e996fc82a94ccd30074c95df7\
+             63722017069e5268da5d1c0b4f872cf653c11df82314a67968dfeae28def04bb\
+             6d84b1c31d654a1970e5783bd6eb96a024c2ca2f4a90fe9f2ef5c9c140e5bb48\
+             da9536ad8700c84fc9130adea74e558d51a74ddf85d8b50de96838d6063e0955",
+        );
+    }
+
+    // Não há vetor publicado com SHA-256 para esta chave: o texto cifrado
+    // abaixo foi conferido decifrando-o com a biblioteca `cryptography` do
+    // Python (OAEP com SHA-256 e MGF1-SHA-256).
+    #[test]
+    fn vetor_com_sha256() {
+        conferir_vetor::<Sha256>(
+            "aafd12f659cae63489b479e5076ddec2f06cb58f0a0b0c0d0e0f101112131415",
+            "008d6d07902d690fbd70f75fc6eddc3e913f85781ec12bdeaccf9cdd590498ee\
+             50e1720482a54ee44784868a9a49a4c3428aed715ebf2c4bb553162bc3e8d735\
+             e28019e88633271874b7abc3fe8ab85b4a2071ed644b13bd0d75cef55cf268ad\
+             67c7c310d854b2312c034507a8eaf21538a8998a0cc005a95ee2625827c39934",
+            "58649d378856b8035d05e6a741e24b74c39c307ed3fac25ffcebff8aff57e52e\
+             39b83361ac7ca35a50d4e3c50018207f3a217bbd1512bff1bdc68956029058586\
+             cc874a2fa9c48aa04b2857733d36413bb4ebd94fbb21a57815c8d7e1a3abf8c8\
+             1888fb72ed2272af01cefd94b8c0b145604ca5693193544a598e05b5b4b5a06",
+        );
+    }
+
+    #[test]
+    fn rejeita_rotulo_errado_e_bytes_alterados() {
+        let chave = chave_de_teste_1024();
+        let mensagem = hex_para_octetos(MENSAGEM);
+        let cifrado = criptografar_com_rng::<Sha256, _>(
+            &chave.chave_publica(),
+            &mensagem,
+            Some(b"rotulo"),
+            &mut HmacDrbg::<Sha256>::seed_from_u64(9),
+        )
+        .unwrap();
+        assert_eq!(
+            descriptografar::<Sha256>(&chave, &cifrado, Some(b"rotulo")).unwrap(),
+            mensagem
+        );
+        for rotulo in [None, Some(&b"rotulO"[..])] {
+            assert_eq!(
+                descriptografar::<Sha256>(&chave, &cifrado, rotulo),
+                Err(RsaError::DecodingError)
+            );
+        }
+
+        // O primeiro byte fica de fora: alterá-lo pode levar o texto cifrado
+        // a n ou além, rejeitado antes da decodificação com `InvalidBlock`
+        for posicao in [1, cifrado.len() / 2, cifrado.len() - 1] {
+            let mut alterado = cifrado.clone();
+            alterado[posicao] ^= 0x01;
+            assert_eq!(
+                descriptografar::<Sha256>(&chave, &alterado, Some(b"rotulo")),
+                Err(RsaError::DecodingError)
+            );
+        }
+
+        // Alterações no próprio EM: byte inicial, semente e DB mascarados
+        let k = tamanho_em_bytes(chave.n());
+        let em = codificar::<Sha256, _>(&mensagem, k, None, &mut FonteFixa::new(&[0x42])).unwrap();
+        for posicao in [0, 1, 1 + Sha256::TAMANHO_SAIDA, k - 1] {
+            let mut alterado = em.clone();
+            alterado[posicao] ^= 0x01;
+            assert_eq!(
+                decodificar::<Sha256>(&alterado, None),
+                Err(RsaError::DecodingError)
+            );
+        }
+    }
+}
@@ -0,0 +1,332 @@
+// --------------------------------------------------------
+// RSAES-PKCS1-v1_5 (RFC 8017, seção 7.2)
+// --------------------------------------------------------
+
+use super::{cifrar_octetos, decifrar_octetos, mascara_igual, mascara_menor, selecionar};
+use crate::encoding::{inteiro_para_octetos, tamanho_em_bytes};
+use crate::error::RsaError;
+use crate::hash::{hmac, FuncaoHash, Sha256};
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+use rand::Rng;
+
+/// Tamanho mínimo da sequência de preenchimento aleatório PS.
+const TAMANHO_MINIMO_PS: usize = 8;
+
+/// Bytes fixos do formato: 0x00 || 0x02 || PS || 0x00 || M.
+const SOBRECARGA: usize = TAMANHO_MINIMO_PS + 3;
+
+/// Maior mensagem que cabe em um bloco PKCS#1 v1.5 de `k` bytes.
+pub fn tamanho_maximo_mensagem(k: usize) -> usize {
+    k.saturating_sub(SOBRECARGA)
+}
+
+/// Cifra `mensagem` com RSAES-PKCS1-v1_5.
+pub fn criptografar(chave: &RsaPublicKey, mensagem: &[u8]) -> Result<Vec<u8>, RsaError> {
+    criptografar_com_rng(chave, mensagem, &mut rand::thread_rng())
+}
+
+/// Variante de `criptografar` que sorteia o preenchimento a partir de `rng`.
+pub fn criptografar_com_rng<R: RandomSource + ?Sized>(
+    chave: &RsaPublicKey,
+    mensagem: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(&chave.n);
+    let em = codificar(mensagem, k, rng)?;
+    cifrar_octetos(chave, &em)
+}
+
+/// EME-PKCS1-v1_5: EM = 0x00 || 0x02 || PS || 0x00 || M, com PS formado por
+/// pelo menos 8 bytes aleatórios não nulos.
+///
+/// Retorna `RsaError::MessageTooLong` se `mensagem` tiver mais de k − 11 bytes.
+pub fn codificar<R: RandomSource + ?Sized>(
+    mensagem: &[u8],
+    k: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    if k < SOBRECARGA || mensagem.len() > tamanho_maximo_mensagem(k) {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    let tamanho_ps = k - mensagem.len() - 3;
+    let mut em = Vec::with_capacity(k);
+    em.extend_from_slice(&[0x00, 0x02]);
+    em.extend((0..tamanho_ps).map(|_| rng.gen_range(1..=255u8)));
+    em.push(0x00);
+    em.extend_from_slice(mensagem);
+    Ok(em)
+}
+
+/// Decifra com verificação estrita do preenchimento.
+///
+/// Retorna `RsaError::DecodingError` quando o preenchimento é inválido. Expor
+/// essa distinção a um atacante cria o oráculo de Bleichenbacher; servidores
+/// devem preferir `descriptografar_rejeicao_implicita`.
+pub fn descriptografar(chave: &RsaPrivateKey, cifrado: &[u8]) -> Result<Vec<u8>, RsaError> {
+    descriptografar_com_rng(chave, cifrado, &mut rand::thread_rng())
+}
+
+/// Variante de `descriptografar` que sorteia o cegamento a partir de `rng`.
+pub fn descriptografar_com_rng<R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let em = decifrar_octetos(chave, cifrado, rng)?;
+    decodificar(&em)
+}
+
+/// Inverso de `codificar`: exige 0x00 || 0x02, pelo menos 8 bytes de PS sem
+/// zeros e o separador 0x00.
+pub fn decodificar(em: &[u8]) -> Result<Vec<u8>, RsaError> {
+    if em.len() < SOBRECARGA || em[0] != 0x00 || em[1] != 0x02 {
+        return Err(RsaError::DecodingError);
+    }
+
+    let separador = em[2..]
+        .iter()
+        .position(|&byte| byte == 0x00)
+        .map(|posicao| posicao + 2)
+        .ok_or(RsaError::DecodingError)?;
+    if separador - 2 < TAMANHO_MINIMO_PS {
+        return Err(RsaError::DecodingError);
+    }
+    Ok(em[separador + 1..].to_vec())
+}
+
+/// Decifra com rejeição implícita (draft-irtf-cfrg-rsa-guidance).
+///
+/// Quando o preenchimento é inválido, em vez de um erro é devolvida uma
+/// mensagem sintética derivada de forma determinística de `d` e do texto
+/// cifrado, de modo que o chamador não consegue distinguir os dois casos.
+/// A verificação do preenchimento e a escolha do resultado não dependem de
+/// desvios condicionais sobre os dados secretos.
+pub fn descriptografar_rejeicao_implicita(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    descriptografar_rejeicao_implicita_com_rng(chave, cifrado, &mut rand::thread_rng())
+}
+
+/// Variante de `descriptografar_rejeicao_implicita` que sorteia o cegamento
+/// a partir de `rng`; a mensagem sintética não depende dele.
+pub fn descriptografar_rejeicao_implicita_com_rng<R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    cifrado: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(chave.n());
+    if k < SOBRECARGA {
+        return Err(RsaError::InvalidKey);
+    }
+    let em = decifrar_octetos(chave, cifrado, rng)?;
+
+    // Chave de derivação: KDK = HMAC-SHA256(SHA256(d), C)
+    let d = inteiro_para_octetos(chave.d(), k)?;
+    let kdk = hmac::<Sha256>(&Sha256::digest(&d), cifrado);
+
+    let mensagem_sintetica = prf(&kdk, b"message", k * 8);
+    let tamanho_sintetico = escolher_tamanho_sintetico(&kdk, k);
+
+    // Localiza o separador sem desvios que dependam de `em`
+    let mut valido = mascara_igual(em[0], 0x00) & mascara_igual(em[1], 0x02);
+    let mut achou_zero = 0usize;
+    let mut separador = 0usize;
+    for (i, &byte) in em.iter().enumerate().skip(2) {
+        let eh_zero = mascara_igual(byte, 0x00);
+        separador = selecionar(eh_zero & !achou_zero, i, separador);
+        achou_zero |= eh_zero;
+    }
+    valido &= achou_zero & !mascara_menor(separador, TAMANHO_MINIMO_PS + 2);
+
+    // Ambas as mensagens ficam alinhadas ao final de um buffer de k bytes
+    let tamanho = selecionar(valido, k - separador - 1, tamanho_sintetico);
+    let buffer: Vec<u8> = em
+        .iter()
+        .zip(&mensagem_sintetica)
+        .map(|(&real, &sintetico)| selecionar(valido, real as usize, sintetico as usize) as u8)
+        .collect();
+
+    Ok(buffer[k - tamanho..].to_vec())
+}
+
+/// Sorteia, a partir da KDK, o tamanho da mensagem sintética: o último dos 128
+/// candidatos de 16 bits (mascarados) que for menor que k − 10.
+fn escolher_tamanho_sintetico(kdk: &[u8], k: usize) -> usize {
+    let limite = k - 2 - TAMANHO_MINIMO_PS;
+    let mut mascara = limite;
+    for deslocamento in [1, 2, 4, 8] {
+        mascara |= mascara >> deslocamento;
+    }
+
+    let candidatos = prf(kdk, b"length", 128 * 2 * 8);
+    candidatos.chunks_exact(2).fold(0, |escolhido, par| {
+        let candidato = (usize::from(par[0]) << 8 | usize::from(par[1])) & mascara;
+        selecionar(mascara_menor(candidato, limite), candidato, escolhido)
+    })
+}
+
+/// Função pseudoaleatória da rejeição implícita: concatena
+/// HMAC-SHA256(KDK, I || rótulo || bits) para I = 0, 1, ... até `bits` bits.
+fn prf(kdk: &[u8], rotulo: &[u8], bits: usize) -> Vec<u8> {
+    let tamanho = bits / 8;
+    let mut saida = Vec::with_capacity(tamanho + Sha256::TAMANHO_SAIDA);
+
+    let mut contador: u16 = 0;
+    while saida.len() < tamanho {
+        let mut entrada = contador.to_be_bytes().to_vec();
+        entrada.extend_from_slice(rotulo);
+        entrada.extend_from_slice(&(bits as u16).to_be_bytes());
+        saida.extend(hmac::<Sha256>(kdk, &entrada));
+        contador += 1;
+    }
+    saida.truncate(tamanho);
+    saida
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{chave_de_teste_1024, hex_para_octetos};
+    use crate::random::HmacDrbg;
+    use rand::{RngCore, SeedableRng};
+
+    // Os textos cifrados foram montados sobre EMs fixos com a chave de
+    // `chave_de_teste_1024`, e as saídas esperadas, obtidas com o
+    // `openssl pkeyutl -decrypt` do OpenSSL 3.5, que implementa a rejeição
+    // implícita do draft-irtf-cfrg-rsa-guidance por padrão.
+    fn decifrar_nos_dois_modos(cifrado: &str) -> (Result<Vec<u8>, RsaError>, Vec<u8>) {
+        let chave = chave_de_teste_1024();
+        let cifrado = hex_para_octetos(cifrado);
+        (
+            descriptografar(&chave, &cifrado),
+            descriptografar_rejeicao_implicita(&chave, &cifrado).unwrap(),
+        )
+    }
+
+    #[test]
+    fn rejeicao_implicita_devolve_a_mensagem_de_um_bloco_valido() {
+        let (estrito, implicito) = decifrar_nos_dois_modos(
+            "19c6f5c7fb9642f184eafb1be6c018875a93e6162c67795bd352ac511a2e61b8\
+             412c4d7d0e0f44ca3b32df3b8452c00d8f1d1adbcf9382d259111d79124e3a45\
+             4e8d5a65426373b8543640a161b1e61c856e4d3ca669e06d20b839833139f3da\
+             fb86a1123ac731f003af8dc28b44d9d521154409f585298d8c643878fc39559e",
+        );
+        assert_eq!(estrito.unwrap(), b"rejeicao implicita");
+        assert_eq!(implicito, b"rejeicao implicita");
+    }
+
+    #[test]
+    fn rejeicao_implicita_concorda_com_o_openssl() {
+        let casos = [
+            // EM = 00 02 || PS de 7 bytes || 00 || M
+            (
+                "428f9876feb80a0ef4efeece575ff281733bb671f3be2454d18c25be7d817cc8\
+                 f541fada03d4833ae26e176fafb3227b2f89837ea11cd03bc53588c2a015378b\
+                 cd67f58bbc251ae1fb6d6628688e5fddd296e7b3f182be3c3dbbf9485fd49105\
+                 d92a0228842378d87c95889e710c374463788920f2ecdd4e96acecdf37143ff9",
+                "d171cfdb40994b00deafcd224d3c5bfd10146ea4e7f531fd24ff",
+            ),
+            // EM = 00 02 || PS sem nenhum byte zero
+            (
+                "b899aa6ef7f44826d0d0aa595c602b29513b139ffd5927ee687d2beda4eb9129\
+                 762cebc08c7dcaab6246968ce37961483daf08095c6eb32524a4491477f3b388\
+                 0808982637c3ed656f5adf0b35d729a28f2b163284f1f53167d3799a2b99aecc\
+                 cd6817d2180664b77fa32328d001e5a2aa829292f31259718162321cb0be59fa",
+                "a2463ec40ab331bc833826a63b7c0974a2d405b27f5c3d290d5b3d303770ca9a\
+                 8ab71d6d874ba8e70d63e9598f69c666689db75194d3db94be8de61c8250dc89",
+            ),
+            // EM = 00 01 || PS || 00 || M
+            (
+                "677bffdaa5ef94381363541cb03bd17e158759e34964a44d6618fb7cf816a0e9\
+                 798b048cc35106bf3cb5b8080453e672acbf53db2c0f689791125b8e57474f5b\
+                 62c087e08c133ed92ea164734cd00389c1eb851a69afee6004ef083872a6f940\
+                 a0d787212877beccb4bc307580e8fc3c4df6e907e673cec2e8b8a0d910ef24f6",
+                "20f6e45f3c62ebc5aef56064ae3b8425bf78f7e070116ac1f09f68474f982500\
+                 51e8eaa07d1916a3119164964897d6de93b665f7fd92cfb732a1a3a0a8b7880a\
+                 4215a3bf8c659750dda592e9c5dec3da",
+            ),
+            // Texto cifrado arbitrário
+            (
+                "00ec49f17f2c205644d3a0d242eb5a82ec222ed01fbd8fd6b55d0f2f6ef651e4\
+                 8412c2fc57fd3f936bd53072dbd3a36cd01581b25cffb5ea8d3ba44726b13c86\
+                 1f206c8ba93562d78dcd8b47ceb4f6df933ae59a1a99d1308fff7a0e9cd5dfb0\
+                 08dc47cde569e8675a51606f0a0c7c224f596f885f31a1460c1e5e23734b8e6a",
+                "9cb67a49bb7ac3377ac170310d19300dbab64cc0f5c3b31e4a652444d3f881b6\
+                 693e0b4de8a19a4fccfad85fab32f0b90aaa3a6ac5cc8b388e483ba174f69200\
+                 401968ce7c81",
+            ),
+        ];
+        for (cifrado, sintetica) in casos {
+            let (estrito, implicito) = decifrar_nos_dois_modos(cifrado);
+            assert_eq!(estrito, Err(RsaError::DecodingError));
+            assert_eq!(implicito, hex_para_octetos(sintetica));
+        }
+    }
+
+    #[test]
+    fn cegamento_usa_o_rng_dado() {
+        let cifrado = criptografar(&chave_de_teste_1024().chave_publica(), b"cegamento").unwrap();
+        for implicita in [false, true] {
+            // Chave nova: o primeiro uso sorteia o par de cegamento
+            let mut rng = HmacDrbg::<Sha256>::seed_from_u64(8);
+            let mut intocado = rng.clone();
+            let decifrado = if implicita {
+                descriptografar_rejeicao_implicita_com_rng(
+                    &chave_de_teste_1024(),
+                    &cifrado,
+                    &mut rng,
+                )
+            } else {
+                descriptografar_com_rng(&chave_de_teste_1024(), &cifrado, &mut rng)
+            };
+            assert_eq!(decifrado.unwrap(), b"cegamento");
+            assert_ne!(rng.next_u64(), intocado.next_u64());
+        }
+    }
+
+    #[test]
+    fn decodificar_rejeita_preenchimentos_malformados() {
+        let k = 64;
+        let mensagem = b"abc";
+        let mut valido = vec![0x00, 0x02];
+        valido.extend(std::iter::repeat_n(0xa5, k - mensagem.len() - 3));
+        valido.push(0x00);
+        valido.extend_from_slice(mensagem);
+        assert_eq!(decodificar(&valido).unwrap(), mensagem);
+
+        // PS com 7 bytes, um a menos que o mínimo
+        let mut ps_curto = vec![0x00, 0x02];
+        ps_curto.extend([0xa5; TAMANHO_MINIMO_PS - 1]);
+        ps_curto.push(0x00);
+        ps_curto.resize(k, 0x5a);
+        assert_eq!(decodificar(&ps_curto), Err(RsaError::DecodingError));
+
+        // Com exatamente 8 bytes de PS o bloco é aceito
+        let mut ps_minimo = vec![0x00, 0x02];
+        ps_minimo.extend([0xa5; TAMANHO_MINIMO_PS]);
+        ps_minimo.push(0x00);
+        ps_minimo.resize(k, 0x5a);
+        assert_eq!(decodificar(&ps_minimo).unwrap().len(), k - SOBRECARGA);
+
+        let sem_separador: Vec<u8> = valido
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| if i >= 2 && byte == 0x00 { 0xa5 } else { byte })
+            .collect();
+        assert_eq!(decodificar(&sem_separador), Err(RsaError::DecodingError));
+
+        for cabecalho in [[0x00, 0x01], [0x01, 0x02], [0x02, 0x00]] {
+            let mut errado = valido.clone();
+            errado[..2].copy_from_slice(&cabecalho);
+            assert_eq!(decodificar(&errado), Err(RsaError::DecodingError));
+        }
+
+        assert_eq!(
+            decodificar(&valido[..SOBRECARGA - 1]),
+            Err(RsaError::DecodingError)
+        );
+    }
+}
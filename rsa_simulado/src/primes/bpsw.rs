@@ -0,0 +1,277 @@
+// --------------------------------------------------------
+// Teste de Baillie–PSW
+// --------------------------------------------------------
+
+use super::{crivo_primos_pequenos, MillerRabin};
+use num::Integer;
+use num_bigint::BigInt;
+use num_traits::{One, Signed, Zero};
+
+/// Teste de Baillie–PSW: Miller–Rabin forte na base 2 seguido do teste de
+/// Lucas forte com os parâmetros de Selfridge.
+///
+/// Os dois testes falham em compostos de naturezas diferentes, e nenhum
+/// composto que passe pelos dois é conhecido; abaixo de 2^64 o teste é
+/// comprovadamente exato.
+pub fn eh_primo_bpsw(n: &BigInt) -> bool {
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    if let Some(resultado) = crivo_primos_pequenos(n) {
+        return resultado;
+    }
+    bpsw_sem_divisao(n)
+}
+
+/// Etapas de `eh_primo_bpsw` posteriores à divisão por tentativa, para `n`
+/// ímpar, maior que 3 e já sem fatores pequenos.
+pub(crate) fn bpsw_sem_divisao(n: &BigInt) -> bool {
+    let Some(teste) = MillerRabin::new(n) else {
+        return false;
+    };
+    teste.aprova(&BigInt::from(2)) && teste_lucas_forte(n)
+}
+
+/// Símbolo de Jacobi (a/n); devolve −1, 0 ou 1.
+///
+/// Usa a reciprocidade quadrática e a regra de (2/n), sem fatorar n.
+/// Exige `n` ímpar e positivo, o que `teste_lucas_forte` garante antes de
+/// chamá-lo; fora disso, o resultado não tem significado.
+pub(crate) fn simbolo_jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    debug_assert!(
+        n.is_positive() && n.is_odd(),
+        "n precisa ser ímpar e positivo"
+    );
+
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut resultado = 1;
+
+    while !a.is_zero() {
+        // (2/n) = −1 quando n ≡ 3 ou 5 (mod 8)
+        let zeros = a.trailing_zeros().unwrap_or(0);
+        a >>= zeros;
+        let n_mod_8 = resto_pequeno(&n, 8);
+        if zeros % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            resultado = -resultado;
+        }
+
+        // Reciprocidade: troca o sinal se a ≡ n ≡ 3 (mod 4)
+        std::mem::swap(&mut a, &mut n);
+        if resto_pequeno(&a, 4) == 3 && resto_pequeno(&n, 4) == 3 {
+            resultado = -resultado;
+        }
+        a = a.mod_floor(&n);
+    }
+
+    if n.is_one() {
+        resultado
+    } else {
+        0
+    }
+}
+
+/// Teste de Lucas forte com os parâmetros de Selfridge (método A).
+///
+/// Escolhe o primeiro D em 5, −7, 9, −11, ... com (D/n) = −1, toma P = 1 e
+/// Q = (1 − D)/4 e escreve n + 1 = d·2^s. n é provável primo de Lucas forte
+/// se U_d ≡ 0 ou V_(d·2^r) ≡ 0 (mod n) para algum 0 ≤ r < s.
+///
+/// Espera `n` sem fatores pequenos; devolve `false` se `n` for par
+/// (inclusive 2) ou menor que 3.
+pub fn teste_lucas_forte(n: &BigInt) -> bool {
+    if *n < BigInt::from(3) || n.is_even() {
+        return false;
+    }
+
+    // Sem esta verificação, a busca por D nunca termina para quadrados perfeitos
+    let raiz = n.sqrt();
+    if &raiz * &raiz == *n {
+        return false;
+    }
+
+    let mut d_selfridge = BigInt::from(5);
+    loop {
+        match simbolo_jacobi(&d_selfridge, n) {
+            -1 => break,
+            0 if d_selfridge.abs() != *n => return false,
+            _ => {}
+        }
+        // 5, −7, 9, −11, ...
+        let dois = BigInt::from(2);
+        d_selfridge = if d_selfridge.is_positive() {
+            -(d_selfridge + dois)
+        } else {
+            dois - d_selfridge
+        };
+    }
+    // D ≡ 1 (mod 4), então a divisão é exata
+    let q = ((BigInt::one() - &d_selfridge) / BigInt::from(4)).mod_floor(n);
+    let d_selfridge = d_selfridge.mod_floor(n);
+
+    let n_mais_1: BigInt = n + 1;
+    let s = n_mais_1.trailing_zeros().unwrap_or(0);
+    let expoente = &n_mais_1 >> s;
+
+    // Cadeia binária sobre os bits de d, partindo de U_1 = 1, V_1 = P = 1, Q^1
+    let mut u = BigInt::one();
+    let mut v = BigInt::one();
+    let mut q_k = q.clone();
+    for i in (0..expoente.bits() - 1).rev() {
+        // Duplicação: U_2k = U_k·V_k, V_2k = V_k² − 2·Q^k
+        u = (&u * &v) % n;
+        v = duplicar_v(&v, &q_k, n);
+        q_k = (&q_k * &q_k) % n;
+
+        if expoente.bit(i) {
+            // Avanço: U_(k+1) = (P·U + V)/2, V_(k+1) = (D·U + P·V)/2
+            let novo_u = metade_mod(&(&u + &v), n);
+            let novo_v = metade_mod(&(&d_selfridge * &u + &v), n);
+            u = novo_u;
+            v = novo_v;
+            q_k = (&q_k * &q) % n;
+        }
+    }
+
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = duplicar_v(&v, &q_k, n);
+        if v.is_zero() {
+            return true;
+        }
+        q_k = (&q_k * &q_k) % n;
+    }
+    false
+}
+
+/// V_2k = V_k² − 2·Q^k mod n.
+fn duplicar_v(v: &BigInt, q_k: &BigInt, n: &BigInt) -> BigInt {
+    let dobro: BigInt = q_k << 1;
+    (v * v - dobro).mod_floor(n)
+}
+
+/// x/2 mod n para `n` ímpar: soma n quando x é ímpar para dividir exatamente.
+fn metade_mod(x: &BigInt, n: &BigInt) -> BigInt {
+    let x = x.mod_floor(n);
+    if x.is_odd() {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
+
+/// Resto de `x` (não negativo) por uma potência de 2 pequena.
+fn resto_pequeno(x: &BigInt, modulo: u32) -> u32 {
+    let (_, digitos) = x.to_u32_digits();
+    digitos
+        .first()
+        .map_or(0, |&menos_significativo| menos_significativo % modulo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primes::{eh_primo, gerar_primo_com_teste, TestePrimalidade};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// (a/n) pela definição: produto dos símbolos de Legendre dos fatores de
+    /// n, cada um pelo critério de Euler.
+    fn jacobi_ingenuo(a: i64, n: i64) -> i32 {
+        let mut resultado = 1;
+        let mut resto = n;
+        let mut p = 3;
+        while resto > 1 {
+            while resto % p == 0 {
+                let base = a.rem_euclid(p);
+                let potencia = (0..(p - 1) / 2).fold(1, |x, _| x * base % p);
+                resultado *= match potencia {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                resto /= p;
+            }
+            p += 2;
+        }
+        resultado
+    }
+
+    #[test]
+    fn simbolo_jacobi_concorda_com_a_definicao() {
+        for n in (1..300i64).step_by(2) {
+            for a in -60..360i64 {
+                assert_eq!(
+                    simbolo_jacobi(&BigInt::from(a), &BigInt::from(n)),
+                    jacobi_ingenuo(a, n),
+                    "({}/{})",
+                    a,
+                    n
+                );
+            }
+        }
+        for (a, n, esperado) in [(1001, 9907, -1), (19, 45, 1), (8, 21, -1), (5, 21, 1)] {
+            assert_eq!(simbolo_jacobi(&BigInt::from(a), &BigInt::from(n)), esperado);
+        }
+    }
+
+    #[test]
+    fn pseudoprimos_de_lucas_fortes_passam_no_lucas_mas_nao_no_bpsw() {
+        // Primeiros pseudoprimos de Lucas fortes com os parâmetros de
+        // Selfridge (OEIS A217255)
+        for n in [
+            5459, 5777, 10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519,
+        ] {
+            let n = BigInt::from(n);
+            assert!(teste_lucas_forte(&n), "{}", n);
+            assert!(!eh_primo_bpsw(&n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn lucas_forte_rejeita_n_par_ou_menor_que_3() {
+        for n in [-7, -1, 0, 1, 2, 4, 5460, 1 << 20] {
+            assert!(!teste_lucas_forte(&BigInt::from(n)), "{}", n);
+        }
+        for n in [3, 5, 7, 65537] {
+            assert!(teste_lucas_forte(&BigInt::from(n)), "{}", n);
+        }
+    }
+
+    #[test]
+    fn bpsw_concorda_com_miller_rabin() {
+        for n in 0..20000u32 {
+            let n = BigInt::from(n);
+            assert_eq!(eh_primo_bpsw(&n), eh_primo(&n, 0), "{}", n);
+        }
+        for n in [561u64, 1105, 1729, 2047, 3215031751, 3825123056546413051] {
+            assert!(!eh_primo_bpsw(&BigInt::from(n)), "{}", n);
+        }
+        let mersenne_127: BigInt = (BigInt::one() << 127) - 1;
+        assert!(eh_primo_bpsw(&mersenne_127));
+        assert!(!eh_primo_bpsw(&(&mersenne_127 * &mersenne_127)));
+    }
+
+    #[test]
+    fn testemunhas_fixas_abaixo_de_2_64_dispensam_rodadas() {
+        // Com k = 0 não há testemunhas aleatórias: a resposta vem só de
+        // `TESTEMUNHAS_DETERMINISTICAS_64`
+        assert!(!eh_primo(&BigInt::from(3825123056546413051u64), 0));
+        assert!(!eh_primo(&BigInt::from(3215031751u64), 0));
+        assert!(eh_primo(&BigInt::from(18446744073709551557u64), 0));
+        assert!(eh_primo(&BigInt::from(4294967291u64), 0));
+    }
+
+    #[test]
+    fn geracao_com_bpsw_produz_primos() {
+        let mut rng = StdRng::seed_from_u64(22);
+        for bits in [64, 256, 512] {
+            let primo =
+                gerar_primo_com_teste(bits, TestePrimalidade::BailliePsw, &mut rng).unwrap();
+            assert_eq!(primo.bits(), u64::from(bits));
+            assert!(eh_primo(&primo, 40));
+        }
+    }
+}
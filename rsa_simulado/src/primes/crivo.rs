@@ -0,0 +1,278 @@
+// --------------------------------------------------------
+// Crivo Incremental para a Busca de Primos
+// --------------------------------------------------------
+
+use crate::error::RsaError;
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::sync::OnceLock;
+
+/// Quantidade de primos ímpares cujos restos o crivo acompanha.
+pub const QUANTIDADE_PRIMOS_CRIVO: usize = 2048;
+
+/// Maior deslocamento, a partir da base, examinado antes de desistir: limita
+/// o viés para primos após lacunas longas.
+const DESLOCAMENTO_MAXIMO: u64 = 1 << 20;
+
+/// Candidatos ímpares peneirados de uma vez (a distância média até o próximo
+/// primo de 2048 bits é de cerca de 710 ímpares).
+const TAMANHO_JANELA: u64 = 1024;
+
+/// Os `QUANTIDADE_PRIMOS_CRIVO` primeiros primos ímpares, calculados uma única
+/// vez pelo crivo de Eratóstenes.
+pub fn primos_crivo() -> &'static [u32] {
+    static PRIMOS: OnceLock<Vec<u32>> = OnceLock::new();
+    PRIMOS.get_or_init(|| {
+        // O 2049º primo é 17 881; o limite cobre os 2048 ímpares com folga
+        let limite = 18_000;
+        let mut composto = vec![false; limite];
+        let mut primos = Vec::with_capacity(QUANTIDADE_PRIMOS_CRIVO);
+        for i in 3..limite {
+            if composto[i] || i.is_multiple_of(2) {
+                continue;
+            }
+            primos.push(i as u32);
+            if primos.len() == QUANTIDADE_PRIMOS_CRIVO {
+                break;
+            }
+            for multiplo in (i * i..limite).step_by(2 * i) {
+                composto[multiplo] = true;
+            }
+        }
+        primos
+    })
+}
+
+/// Divisão por tentativa ingênua pelos mesmos primos de `primos_crivo`, um
+/// resto de número grande por primo. Serve de referência para o crivo.
+///
+/// Retorna `Some(true)` se `n` é um desses primos, `Some(false)` se é menor
+/// que 2, par ou tem um deles como fator e `None` quando não é conclusiva.
+pub fn divisao_por_tentativa(n: &BigInt) -> Option<bool> {
+    if *n == BigInt::from(2) {
+        return Some(true);
+    }
+    if *n < BigInt::from(2) || !n.bit(0) {
+        return Some(false);
+    }
+    for &primo in primos_crivo() {
+        let primo = BigInt::from(primo);
+        if *n == primo {
+            return Some(true);
+        }
+        if (n % &primo).is_zero() {
+            return Some(false);
+        }
+    }
+    None
+}
+
+/// Crivo incremental a partir de uma base ímpar: os restos da base módulo os
+/// primos de `primos_crivo` são calculados uma única vez e, a partir deles, o
+/// índice do próximo múltiplo de cada primo. Cada janela de candidatos
+/// base + 2i é então peneirada marcando esses múltiplos, como no crivo de
+/// Eratóstenes, sem divisões de números grandes nem restos por candidato.
+#[derive(Debug, Clone)]
+pub struct CrivoIncremental {
+    base: BigInt,
+    /// Para cada primo do crivo, o menor índice i ainda não peneirado com
+    /// base + 2i divisível por ele.
+    multiplos: Vec<u64>,
+    /// Candidatos da janela atual; `true` marca os que têm fator no crivo.
+    janela: Vec<bool>,
+    /// Índice do primeiro candidato da janela atual.
+    inicio: u64,
+    /// Posição do próximo candidato a examinar na janela.
+    posicao: usize,
+    /// A base é 1, que não é primo mas não tem fatores no crivo.
+    base_um: bool,
+}
+
+impl CrivoIncremental {
+    /// Começa a busca em `base`, arredondada para cima até o próximo ímpar.
+    ///
+    /// Retorna `RsaError::InvalidKey` se `base` for negativa.
+    pub fn new(base: &BigInt) -> Result<Self, RsaError> {
+        if base.is_negative() {
+            return Err(RsaError::InvalidKey);
+        }
+        let mut base = base.clone();
+        if !base.bit(0) {
+            base += 1;
+        }
+        let base_pequena = base.to_u64();
+        let multiplos = primos_crivo()
+            .iter()
+            .map(|&primo| {
+                let primo = u64::from(primo);
+                let resto = resto_por_primo(&base, primo);
+                // base + 2i ≡ 0 (mod p) ⇔ i ≡ −resto · 2⁻¹, com 2⁻¹ = (p + 1)/2
+                let indice = (primo - resto) % primo * primo.div_ceil(2) % primo;
+                // O próprio primo do crivo não é descartado
+                if base_pequena.and_then(|base| base.checked_add(2 * indice)) == Some(primo) {
+                    indice + primo
+                } else {
+                    indice
+                }
+            })
+            .collect();
+        Ok(CrivoIncremental {
+            base_um: base_pequena == Some(1),
+            base,
+            multiplos,
+            janela: Vec::new(),
+            inicio: 0,
+            posicao: 0,
+        })
+    }
+
+    /// Próximo candidato ímpar sem fatores entre os primos do crivo, ou
+    /// `None` depois de `DESLOCAMENTO_MAXIMO`.
+    pub fn proximo(&mut self) -> Option<BigInt> {
+        loop {
+            while self.posicao < self.janela.len() {
+                let posicao = self.posicao;
+                self.posicao += 1;
+                if !self.janela[posicao] {
+                    return Some(&self.base + 2 * (self.inicio + posicao as u64));
+                }
+            }
+
+            let proximo_inicio = self.inicio + self.janela.len() as u64;
+            if proximo_inicio > DESLOCAMENTO_MAXIMO / 2 {
+                return None;
+            }
+            self.peneirar(proximo_inicio);
+        }
+    }
+
+    /// Marca, na janela que começa no índice `inicio`, os candidatos
+    /// divisíveis por algum primo do crivo.
+    fn peneirar(&mut self, inicio: u64) {
+        let fim = (inicio + TAMANHO_JANELA).min(DESLOCAMENTO_MAXIMO / 2 + 1);
+        self.janela.clear();
+        self.janela.resize((fim - inicio) as usize, false);
+        for (&primo, multiplo) in primos_crivo().iter().zip(&mut self.multiplos) {
+            while *multiplo < fim {
+                self.janela[(*multiplo - inicio) as usize] = true;
+                *multiplo += u64::from(primo);
+            }
+        }
+        if inicio == 0 && self.base_um {
+            self.janela[0] = true;
+        }
+        self.inicio = inicio;
+        self.posicao = 0;
+    }
+}
+
+/// Resto de `n` (não negativo) módulo `primo`, pelos dígitos de 64 bits de `n`.
+fn resto_por_primo(n: &BigInt, primo: u64) -> u64 {
+    n.iter_u64_digits().rev().fold(0, |resto, digito| {
+        (((u128::from(resto) << 64) | u128::from(digito)) % u128::from(primo)) as u64
+    })
+}
+
+impl Iterator for CrivoIncremental {
+    type Item = BigInt;
+
+    fn next(&mut self) -> Option<BigInt> {
+        self.proximo()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primes::{eh_primo, RODADAS_MILLER_RABIN};
+    use num_bigint::RandBigInt;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Candidatos ímpares a partir de `base` (arredondada para cima até o
+    /// próximo ímpar) e abaixo de `limite` que a divisão por tentativa não
+    /// descarta.
+    fn sobreviventes_ingenuos(base: &BigInt, limite: &BigInt) -> Vec<BigInt> {
+        let mut candidato = base.clone();
+        if !candidato.bit(0) {
+            candidato += 1;
+        }
+        let mut sobreviventes = Vec::new();
+        while candidato < *limite {
+            if divisao_por_tentativa(&candidato) != Some(false) {
+                sobreviventes.push(candidato.clone());
+            }
+            candidato += 2;
+        }
+        sobreviventes
+    }
+
+    fn sobreviventes_do_crivo(base: &BigInt, limite: &BigInt) -> Vec<BigInt> {
+        CrivoIncremental::new(base)
+            .unwrap()
+            .take_while(|candidato| candidato < limite)
+            .collect()
+    }
+
+    #[test]
+    fn crivo_concorda_com_a_divisao_por_tentativa() {
+        let mut rng = StdRng::seed_from_u64(23);
+        for bits in [24u64, 64, 512, 1024] {
+            let base = BigInt::from(rng.gen_biguint(bits)) | (BigInt::from(1) << (bits - 1));
+            let limite = &base + 2 * 2048;
+            let sobreviventes = sobreviventes_do_crivo(&base, &limite);
+            assert!(!sobreviventes.is_empty());
+            assert_eq!(sobreviventes, sobreviventes_ingenuos(&base, &limite));
+        }
+    }
+
+    #[test]
+    fn bases_pequenas_mantem_os_primos_do_crivo_e_descartam_o_1() {
+        for base in [0u32, 1, 2, 3, 4, 9, 17_879, 17_881, 17_882] {
+            let base = BigInt::from(base);
+            let limite = &base + 4000;
+            let sobreviventes = sobreviventes_do_crivo(&base, &limite);
+            assert_eq!(sobreviventes, sobreviventes_ingenuos(&base, &limite));
+            assert!(!sobreviventes.contains(&BigInt::from(1)));
+            // Abaixo de 17 881², sobreviver ao crivo é o mesmo que ser primo
+            for candidato in sobreviventes {
+                assert!(eh_primo(&candidato, RODADAS_MILLER_RABIN), "{}", candidato);
+            }
+        }
+        assert_eq!(divisao_por_tentativa(&BigInt::from(1)), Some(false));
+        assert_eq!(divisao_por_tentativa(&BigInt::from(0)), Some(false));
+    }
+
+    #[test]
+    fn busca_termina_no_deslocamento_maximo() {
+        let mut rng = StdRng::seed_from_u64(23);
+        let base = BigInt::from(rng.gen_biguint(512)) | BigInt::from(1);
+        let ultimo = &base + DESLOCAMENTO_MAXIMO;
+
+        let mut crivo = CrivoIncremental::new(&base).unwrap();
+        let sobreviventes: Vec<BigInt> = crivo.by_ref().collect();
+        assert_eq!(crivo.proximo(), None);
+        assert!(sobreviventes.iter().all(|candidato| *candidato <= ultimo));
+
+        // Os últimos candidatos examinados concordam com a divisão por tentativa
+        let inicio_janela = &ultimo - 2 * 2048;
+        let finais: Vec<BigInt> = sobreviventes
+            .into_iter()
+            .filter(|candidato| *candidato >= inicio_janela)
+            .collect();
+        assert_eq!(
+            finais,
+            sobreviventes_ingenuos(&inicio_janela, &(&ultimo + 1))
+        );
+    }
+
+    #[test]
+    fn base_negativa_e_rejeitada() {
+        for base in [-1, -2, -17_881] {
+            assert_eq!(
+                CrivoIncremental::new(&BigInt::from(base)).map(|_| ()),
+                Err(RsaError::InvalidKey)
+            );
+        }
+    }
+}
@@ -0,0 +1,331 @@
+// --------------------------------------------------------
+// Primos Seguros e Primos Fortes
+// --------------------------------------------------------
+
+use super::{crivo_primos_pequenos, eh_primo_com_rng, gerar_primo_com_teste, TestePrimalidade};
+use crate::arithmetic::exponenciacao_modular;
+use crate::error::RsaError;
+use crate::random::RandomSource;
+use num::Integer;
+use num_bigint::{BigInt, RandBigInt};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Menor primo forte aceito por `gerar_primo_forte`, em bits: abaixo disso os
+/// primos auxiliares s e t ficam pequenos demais para fazer diferença.
+pub const TAMANHO_MINIMO_PRIMO_FORTE: u32 = 64;
+
+/// Menor primo seguro aceito por `gerar_primo_seguro`, em bits: com os dois
+/// bits mais altos de q ligados, não há primos seguros de 4 ou 5 bits.
+pub const TAMANHO_MINIMO_PRIMO_SEGURO: u32 = 6;
+
+/// Bits do índice inicial sorteado na busca de r = 2·i·t + 1.
+const BITS_INDICE_GORDON: u64 = 12;
+
+/// Tipo de primo sorteado na geração de chaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TipoPrimo {
+    /// Primo aleatório comum, de `gerar_primo`.
+    #[default]
+    Aleatorio,
+    /// Primo seguro p = 2q + 1, com q primo.
+    Seguro,
+    /// Primo forte pelo algoritmo de Gordon.
+    Forte,
+}
+
+impl TipoPrimo {
+    /// Gera um primo deste tipo com exatamente `bits` bits.
+    pub fn gerar(self, bits: u32) -> Result<BigInt, RsaError> {
+        self.gerar_com_rng(bits, &mut rand::thread_rng())
+    }
+
+    /// Variante de `gerar` que usa `rng` como fonte de aleatoriedade.
+    pub fn gerar_com_rng<R: RandomSource + ?Sized>(
+        self,
+        bits: u32,
+        rng: &mut R,
+    ) -> Result<BigInt, RsaError> {
+        self.gerar_com_teste(bits, TestePrimalidade::default(), rng)
+    }
+
+    /// Variante de `gerar_com_rng` que aprova os candidatos com o `teste` dado.
+    pub fn gerar_com_teste<R: RandomSource + ?Sized>(
+        self,
+        bits: u32,
+        teste: TestePrimalidade,
+        rng: &mut R,
+    ) -> Result<BigInt, RsaError> {
+        match self {
+            TipoPrimo::Aleatorio => gerar_primo_com_teste(bits, teste, rng),
+            TipoPrimo::Seguro => gerar_primo_seguro_com_teste(bits, teste, rng),
+            TipoPrimo::Forte => gerar_primo_forte_com_teste(bits, teste, rng).map(|forte| forte.p),
+        }
+    }
+}
+
+/// Gera um primo seguro p = 2q + 1 de `bits` bits, com q também primo.
+///
+/// Como p − 1 = 2q, o método p − 1 de Pollard não encontra fatores de p.
+/// Retorna `RsaError::InvalidBitLength` se `bits` for menor que
+/// `TAMANHO_MINIMO_PRIMO_SEGURO`.
+pub fn gerar_primo_seguro(bits: u32) -> Result<BigInt, RsaError> {
+    gerar_primo_seguro_com_rng(bits, &mut rand::thread_rng())
+}
+
+/// Variante de `gerar_primo_seguro` que usa `rng` como fonte de aleatoriedade.
+pub fn gerar_primo_seguro_com_rng<R: RandomSource + ?Sized>(
+    bits: u32,
+    rng: &mut R,
+) -> Result<BigInt, RsaError> {
+    gerar_primo_seguro_com_teste(bits, TestePrimalidade::default(), rng)
+}
+
+/// Variante de `gerar_primo_seguro_com_rng` que confirma q e p com o `teste` dado.
+///
+/// O crivo e uma rodada de Miller–Rabin são aplicados a q e a p antes do
+/// teste completo, já que quase todos os candidatos caem nessas etapas.
+pub fn gerar_primo_seguro_com_teste<R: RandomSource + ?Sized>(
+    bits: u32,
+    teste: TestePrimalidade,
+    rng: &mut R,
+) -> Result<BigInt, RsaError> {
+    if bits < TAMANHO_MINIMO_PRIMO_SEGURO {
+        return Err(RsaError::InvalidBitLength(bits));
+    }
+
+    loop {
+        // q com bits − 1 bits e os dois mais altos ligados, como em `gerar_primo`
+        let tamanho_q = u64::from(bits - 1);
+        let mut q = BigInt::from(rng.gen_biguint(tamanho_q));
+        q.set_bit(tamanho_q - 1, true);
+        q.set_bit(tamanho_q - 2, true);
+        q.set_bit(0, true);
+        let p = 2 * &q + 1;
+
+        if crivo_primos_pequenos(&q) == Some(false) || crivo_primos_pequenos(&p) == Some(false) {
+            continue;
+        }
+        if !eh_primo_com_rng(&q, 1, rng) || !eh_primo_com_rng(&p, 1, rng) {
+            continue;
+        }
+        if teste.testar_com_rng(&q, rng) && teste.testar_com_rng(&p, rng) {
+            return Ok(p);
+        }
+    }
+}
+
+/// Primo forte p e os primos auxiliares da sua construção: r divide p − 1,
+/// s divide p + 1 e t divide r − 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimoForte {
+    pub p: BigInt,
+    pub r: BigInt,
+    pub s: BigInt,
+    pub t: BigInt,
+}
+
+/// Gera um primo forte de `bits` bits pelo algoritmo de Gordon.
+///
+/// Os fatores grandes de p − 1 e p + 1 tornam inúteis os métodos p − 1 de
+/// Pollard e p + 1 de Williams. Retorna `RsaError::InvalidBitLength` se
+/// `bits` for menor que `TAMANHO_MINIMO_PRIMO_FORTE`.
+pub fn gerar_primo_forte(bits: u32) -> Result<PrimoForte, RsaError> {
+    gerar_primo_forte_com_rng(bits, &mut rand::thread_rng())
+}
+
+/// Variante de `gerar_primo_forte` que usa `rng` como fonte de aleatoriedade.
+///
+/// 1. Sorteia os primos s e t.
+/// 2. Acha o primeiro primo r = 2·i·t + 1 a partir de um i aleatório.
+/// 3. Calcula p0 = 2·(s^(r−2) mod r)·s − 1, de modo que p0 ≡ 1 (mod r) e
+///    p0 ≡ −1 (mod s).
+/// 4. Acha o primeiro primo p = p0 + 2·j·r·s com `bits` bits e os dois bits
+///    mais altos ligados.
+pub fn gerar_primo_forte_com_rng<R: RandomSource + ?Sized>(
+    bits: u32,
+    rng: &mut R,
+) -> Result<PrimoForte, RsaError> {
+    gerar_primo_forte_com_teste(bits, TestePrimalidade::default(), rng)
+}
+
+/// Variante de `gerar_primo_forte_com_rng` que aprova p e os primos
+/// auxiliares com o `teste` dado.
+pub fn gerar_primo_forte_com_teste<R: RandomSource + ?Sized>(
+    bits: u32,
+    teste: TestePrimalidade,
+    rng: &mut R,
+) -> Result<PrimoForte, RsaError> {
+    if bits < TAMANHO_MINIMO_PRIMO_FORTE {
+        return Err(RsaError::InvalidBitLength(bits));
+    }
+
+    // s e t com cerca de metade de p, deixando ~20 bits de folga para j
+    let tamanho_auxiliar = (bits - 34) / 2;
+    let limite_inferior = BigInt::from(3) << (bits - 2);
+
+    loop {
+        let s = gerar_primo_com_teste(tamanho_auxiliar, teste, rng)?;
+        let t = gerar_primo_com_teste(tamanho_auxiliar, teste, rng)?;
+
+        let mut i = BigInt::from(rng.gen_biguint(BITS_INDICE_GORDON)) + 1;
+        let r = loop {
+            let candidato = 2 * &i * &t + 1;
+            if teste.testar_com_rng(&candidato, rng) {
+                break candidato;
+            }
+            i += 1;
+        };
+
+        let p0 = 2 * exponenciacao_modular(&s, &(&r - 2), &r)? * &s - 1;
+        let passo = 2 * &r * &s;
+        let falta: BigInt = &limite_inferior - &p0;
+        let j = falta.div_ceil(&passo).max(BigInt::from(0));
+
+        let mut p: BigInt = p0 + j * &passo;
+        while p.bits() == u64::from(bits) {
+            if teste.testar_com_rng(&p, rng) {
+                return Ok(PrimoForte { p, r, s, t });
+            }
+            p += &passo;
+        }
+    }
+}
+
+/// Estatísticas de tempo de `medir_geracao`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstatisticasTempo {
+    pub amostras: u32,
+    pub media: Duration,
+    pub minimo: Duration,
+    pub maximo: Duration,
+    pub desvio_padrao: Duration,
+}
+
+impl fmt::Display for EstatisticasTempo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} amostras: média {:?} ± {:?} (mín. {:?}, máx. {:?})",
+            self.amostras, self.media, self.desvio_padrao, self.minimo, self.maximo
+        )
+    }
+}
+
+/// Gera `amostras` primos do `tipo` dado, com `bits` bits, e resume os tempos.
+///
+/// Pelo menos uma amostra é sempre gerada.
+pub fn medir_geracao(
+    tipo: TipoPrimo,
+    bits: u32,
+    amostras: u32,
+) -> Result<EstatisticasTempo, RsaError> {
+    let amostras = amostras.max(1);
+    let mut tempos = Vec::with_capacity(amostras as usize);
+    for _ in 0..amostras {
+        let inicio = Instant::now();
+        tipo.gerar(bits)?;
+        tempos.push(inicio.elapsed().as_secs_f64());
+    }
+
+    let media = tempos.iter().sum::<f64>() / f64::from(amostras);
+    let variancia = tempos.iter().map(|t| (t - media).powi(2)).sum::<f64>() / f64::from(amostras);
+    let minimo = tempos.iter().copied().fold(f64::INFINITY, f64::min);
+    let maximo = tempos.iter().copied().fold(0.0, f64::max);
+
+    Ok(EstatisticasTempo {
+        amostras,
+        media: Duration::from_secs_f64(media),
+        minimo: Duration::from_secs_f64(minimo),
+        maximo: Duration::from_secs_f64(maximo),
+        desvio_padrao: Duration::from_secs_f64(variancia.sqrt()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primes::{eh_primo, RODADAS_MILLER_RABIN};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn primo(n: &BigInt) -> bool {
+        eh_primo(n, RODADAS_MILLER_RABIN)
+    }
+
+    #[test]
+    fn primo_seguro_tem_metade_de_p_menos_1_prima() {
+        let mut rng = StdRng::seed_from_u64(21);
+        for bits in [TAMANHO_MINIMO_PRIMO_SEGURO, 7, 32, 64, 128] {
+            let p = gerar_primo_seguro_com_rng(bits, &mut rng).unwrap();
+            assert_eq!(p.bits(), u64::from(bits), "{}", p);
+            assert!(primo(&p) && primo(&((&p - 1) / 2)), "{}", p);
+
+            let p = TipoPrimo::Seguro.gerar_com_rng(bits, &mut rng).unwrap();
+            assert_eq!(p.bits(), u64::from(bits));
+            assert!(primo(&((&p - 1) / 2)), "{}", p);
+        }
+    }
+
+    #[test]
+    fn primo_forte_tem_os_fatores_de_gordon() {
+        let mut rng = StdRng::seed_from_u64(21);
+        for bits in [TAMANHO_MINIMO_PRIMO_FORTE, 65, 128, 256] {
+            let PrimoForte { p, r, s, t } = gerar_primo_forte_com_rng(bits, &mut rng).unwrap();
+            assert_eq!(p.bits(), u64::from(bits), "{}", p);
+            for fator in [&p, &r, &s, &t] {
+                assert!(primo(fator), "{}", fator);
+            }
+            let (p_menos_1, p_mais_1, r_menos_1): (BigInt, BigInt, BigInt) =
+                (&p - 1, &p + 1, &r - 1);
+            assert!(p_menos_1.is_multiple_of(&r));
+            assert!(p_mais_1.is_multiple_of(&s));
+            assert!(r_menos_1.is_multiple_of(&t));
+
+            let p = TipoPrimo::Forte.gerar_com_rng(bits, &mut rng).unwrap();
+            assert_eq!(p.bits(), u64::from(bits));
+        }
+    }
+
+    #[test]
+    fn tamanho_abaixo_do_minimo_e_rejeitado() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let seguro = TAMANHO_MINIMO_PRIMO_SEGURO - 1;
+        let forte = TAMANHO_MINIMO_PRIMO_FORTE - 1;
+        assert_eq!(
+            gerar_primo_seguro_com_rng(seguro, &mut rng),
+            Err(RsaError::InvalidBitLength(seguro))
+        );
+        assert_eq!(
+            gerar_primo_forte_com_rng(forte, &mut rng),
+            Err(RsaError::InvalidBitLength(forte))
+        );
+        assert_eq!(
+            TipoPrimo::Seguro.gerar_com_rng(seguro, &mut rng),
+            Err(RsaError::InvalidBitLength(seguro))
+        );
+        assert_eq!(
+            TipoPrimo::Forte.gerar_com_rng(forte, &mut rng),
+            Err(RsaError::InvalidBitLength(forte))
+        );
+    }
+
+    #[test]
+    fn estatisticas_de_tempo_sao_coerentes() {
+        for (tipo, amostras, esperadas) in [
+            (TipoPrimo::Aleatorio, 5, 5),
+            (TipoPrimo::Seguro, 3, 3),
+            (TipoPrimo::Forte, 0, 1),
+        ] {
+            let estatisticas = medir_geracao(tipo, 64, amostras).unwrap();
+            assert_eq!(estatisticas.amostras, esperadas);
+            assert!(estatisticas.minimo <= estatisticas.media);
+            assert!(estatisticas.media <= estatisticas.maximo);
+            assert!(estatisticas.desvio_padrao <= estatisticas.maximo - estatisticas.minimo);
+        }
+        assert_eq!(
+            medir_geracao(TipoPrimo::Forte, 32, 3),
+            Err(RsaError::InvalidBitLength(32))
+        );
+    }
+}
@@ -0,0 +1,346 @@
+// --------------------------------------------------------
+// Teste de Primalidade e Geração de Primos
+// --------------------------------------------------------
+
+pub mod bpsw;
+pub mod crivo;
+pub mod especiais;
+
+use crate::arithmetic::montgomery::ContextoMontgomery;
+use crate::error::RsaError;
+use crate::random::RandomSource;
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+use std::ops::Shr;
+
+/// Número de rodadas do Miller–Rabin usado na verificação dos primos.
+pub const RODADAS_MILLER_RABIN: u32 = 40;
+
+/// Primos menores que 256, usados na divisão por tentativa antes do Miller–Rabin.
+pub const PRIMOS_PEQUENOS: [u32; 54] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+];
+
+/// Crivo por divisão por tentativa com os primos pequenos.
+/// Retorna `Some(true)` se `n` é um desses primos, `Some(false)` se tem um deles
+/// como fator e `None` quando o crivo não é conclusivo.
+pub(crate) fn crivo_primos_pequenos(n: &BigInt) -> Option<bool> {
+    for &primo in PRIMOS_PEQUENOS.iter() {
+        let primo = BigInt::from(primo);
+        if *n == primo {
+            return Some(true);
+        }
+        if (n % &primo).is_zero() {
+            return Some(false);
+        }
+    }
+    None
+}
+
+/// Teste de primalidade usado por `eh_primo_com_teste`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestePrimalidade {
+    /// Miller–Rabin com o número de rodadas dado (exato abaixo de 2^64).
+    MillerRabin(u32),
+    /// Baillie–PSW (`bpsw::eh_primo_bpsw`).
+    BailliePsw,
+}
+
+impl Default for TestePrimalidade {
+    fn default() -> Self {
+        TestePrimalidade::MillerRabin(RODADAS_MILLER_RABIN)
+    }
+}
+
+impl TestePrimalidade {
+    /// Decide se `n` é primo, sorteando as testemunhas do Miller–Rabin a
+    /// partir de `rng` (o Baillie–PSW não usa aleatoriedade).
+    pub fn testar_com_rng<R: RandomSource + ?Sized>(self, n: &BigInt, rng: &mut R) -> bool {
+        match self {
+            TestePrimalidade::MillerRabin(k) => eh_primo_com_rng(n, k, rng),
+            TestePrimalidade::BailliePsw => bpsw::eh_primo_bpsw(n),
+        }
+    }
+
+    /// Variante de `testar_com_rng` para os sobreviventes de
+    /// `crivo::CrivoIncremental`: pula a divisão por tentativa, já feita pelo
+    /// crivo com primos maiores, e vai direto ao Miller–Rabin ou ao Baillie–PSW.
+    pub(crate) fn testar_sobrevivente_com_rng<R: RandomSource + ?Sized>(
+        self,
+        n: &BigInt,
+        rng: &mut R,
+    ) -> bool {
+        // Os próprios primos do crivo também sobrevivem, e o Miller–Rabin exige n > 3
+        if n.bits() <= 64 {
+            return self.testar_com_rng(n, rng);
+        }
+        match self {
+            TestePrimalidade::MillerRabin(k) => miller_rabin_com_rng(n, k, rng),
+            TestePrimalidade::BailliePsw => bpsw::bpsw_sem_divisao(n),
+        }
+    }
+}
+
+/// Decide se `n` é primo com o `teste` escolhido pelo chamador.
+pub fn eh_primo_com_teste(n: &BigInt, teste: TestePrimalidade) -> bool {
+    teste.testar_com_rng(n, &mut rand::thread_rng())
+}
+
+/// Testemunhas que tornam o Miller–Rabin determinístico para n < 2^64: nenhum
+/// composto abaixo de 3,3·10^24 passa por todas elas (Sorenson e Webster, 2015).
+pub const TESTEMUNHAS_DETERMINISTICAS_64: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Teste de primalidade de Miller–Rabin com `k` rodadas de testemunhas aleatórias.
+/// A probabilidade de um composto passar é no máximo 4^(-k).
+///
+/// Para n < 2^64, `k` é ignorado e as testemunhas fixas de
+/// `TESTEMUNHAS_DETERMINISTICAS_64` dão a resposta exata. Acima disso, é feita
+/// pelo menos uma rodada mesmo com `k = 0`.
+pub fn eh_primo(n: &BigInt, k: u32) -> bool {
+    eh_primo_com_rng(n, k, &mut rand::thread_rng())
+}
+
+/// Variante de `eh_primo` que sorteia as testemunhas a partir de `rng`.
+pub fn eh_primo_com_rng<R: RandomSource + ?Sized>(n: &BigInt, k: u32, rng: &mut R) -> bool {
+    if *n < BigInt::from(2) {
+        return false;
+    }
+
+    // Divisão por tentativa: descarta rapidamente a maioria dos compostos
+    if let Some(resultado) = crivo_primos_pequenos(n) {
+        return resultado;
+    }
+
+    // Após o crivo, n é ímpar e maior que 256
+    miller_rabin_com_rng(n, k, rng)
+}
+
+/// Rodadas de Miller–Rabin de `eh_primo_com_rng`, sem a divisão por tentativa.
+fn miller_rabin_com_rng<R: RandomSource + ?Sized>(n: &BigInt, k: u32, rng: &mut R) -> bool {
+    let Some(teste) = MillerRabin::new(n) else {
+        return false;
+    };
+
+    if n.bits() <= 64 {
+        return TESTEMUNHAS_DETERMINISTICAS_64
+            .iter()
+            .all(|&a| teste.aprova(&BigInt::from(a)));
+    }
+
+    let limite_testemunha = n - BigInt::one();
+    (0..k.max(1)).all(|_| {
+        // Testemunha a em [2, n - 2]
+        let a = rng.gen_bigint_range(&BigInt::from(2), &limite_testemunha);
+        teste.aprova(&a)
+    })
+}
+
+/// Rodadas de Miller–Rabin para um n ímpar fixo: guarda a decomposição
+/// n − 1 = 2^s · d e o contexto de Montgomery, reaproveitados por todas as
+/// testemunhas.
+pub(crate) struct MillerRabin<'a> {
+    n: &'a BigInt,
+    n_menos_1: BigInt,
+    d: BigInt,
+    s: u64,
+    contexto: ContextoMontgomery,
+}
+
+impl<'a> MillerRabin<'a> {
+    /// Retorna `None` se `n` não for ímpar e maior que 3.
+    pub(crate) fn new(n: &'a BigInt) -> Option<Self> {
+        if *n <= BigInt::from(3) {
+            return None;
+        }
+        let contexto = ContextoMontgomery::new(n).ok()?;
+
+        // Escreve n - 1 = 2^s * d, com d ímpar
+        let n_menos_1 = n - BigInt::one();
+        let s = n_menos_1.trailing_zeros().unwrap_or(0);
+        let d = (&n_menos_1).shr(s);
+        Some(MillerRabin {
+            n,
+            n_menos_1,
+            d,
+            s,
+            contexto,
+        })
+    }
+
+    /// Verdadeiro se n é provável primo forte na base `a`, isto é, se
+    /// a^d ≡ 1 ou a^(d·2^r) ≡ −1 (mod n) para algum 0 ≤ r < s.
+    pub(crate) fn aprova(&self, a: &BigInt) -> bool {
+        let mut x = self.contexto.exponenciar(a, &self.d);
+        if x.is_one() || x == self.n_menos_1 {
+            return true;
+        }
+        for _ in 1..self.s {
+            x = (&x * &x) % self.n;
+            if x == self.n_menos_1 {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Gera um primo aleatório de exatamente `bits` bits.
+pub fn gerar_primo(bits: u32) -> Result<BigInt, RsaError> {
+    gerar_primo_com_rng(bits, &mut rand::thread_rng())
+}
+
+/// Gera um primo de `bits` bits usando `rng` como fonte de aleatoriedade.
+/// Os dois bits mais altos são sempre ligados, de modo que o produto de dois
+/// primos de `bits` bits tenha exatamente `2 * bits` bits.
+/// Retorna `RsaError::InvalidBitLength` se `bits < 2`.
+///
+/// A partir de uma base aleatória, `crivo::CrivoIncremental` descarta os
+/// candidatos com fatores pequenos e só os sobreviventes vão para o
+/// Miller–Rabin, sem repetir a divisão por tentativa de `eh_primo`.
+pub fn gerar_primo_com_rng<R: RandomSource + ?Sized>(
+    bits: u32,
+    rng: &mut R,
+) -> Result<BigInt, RsaError> {
+    gerar_primo_com_teste(bits, TestePrimalidade::default(), rng)
+}
+
+/// Variante de `gerar_primo_com_rng` que aprova os candidatos com o `teste`
+/// dado em vez do Miller–Rabin padrão.
+pub fn gerar_primo_com_teste<R: RandomSource + ?Sized>(
+    bits: u32,
+    teste: TestePrimalidade,
+    rng: &mut R,
+) -> Result<BigInt, RsaError> {
+    if bits < 2 {
+        return Err(RsaError::InvalidBitLength(bits));
+    }
+
+    loop {
+        let mut base = BigInt::from(rng.gen_biguint(u64::from(bits)));
+        base.set_bit(u64::from(bits - 1), true);
+        base.set_bit(u64::from(bits - 2), true);
+        base.set_bit(0, true);
+
+        // Sorteia outra base se a busca passar do tamanho pedido
+        for candidato in crivo::CrivoIncremental::new(&base)? {
+            if candidato.bits() != u64::from(bits) {
+                break;
+            }
+            if teste.testar_sobrevivente_com_rng(&candidato, rng) {
+                return Ok(candidato);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn primo_ingenuo(n: u64) -> bool {
+        n >= 2
+            && (2..)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn concorda_com_divisao_ingenua_abaixo_de_20000() {
+        for n in 0..20_000u64 {
+            assert_eq!(
+                eh_primo(&BigInt::from(n), RODADAS_MILLER_RABIN),
+                primo_ingenuo(n),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn aceita_primos_conhecidos() {
+        let mersenne = |expoente: u32| (BigInt::one() << expoente) - 1;
+        for primo in [
+            BigInt::from(65_537),
+            BigInt::from(4_294_967_291u64),
+            BigInt::from(18_446_744_073_709_551_557u64),
+            mersenne(61),
+            mersenne(89),
+            mersenne(127),
+            mersenne(521),
+        ] {
+            assert!(eh_primo(&primo, RODADAS_MILLER_RABIN), "{}", primo);
+        }
+    }
+
+    #[test]
+    fn rejeita_numeros_de_carmichael() {
+        for n in [561u64, 1105, 1729, 2465, 2821, 6601, 8911, 41_041, 825_265] {
+            assert!(!eh_primo(&BigInt::from(n), RODADAS_MILLER_RABIN), "{}", n);
+        }
+    }
+
+    #[test]
+    fn rejeita_pseudoprimos_fortes() {
+        // 3215031751: base 2, 3, 5 e 7; 3825123056546413051: bases até 23
+        for n in [2047u64, 3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(!eh_primo(&BigInt::from(n), RODADAS_MILLER_RABIN), "{}", n);
+        }
+        // Acima de 2^64: pseudoprimos fortes para as bases primas até 37 e até 41
+        for n in ["318665857834031151167461", "3317044064679887385961981"] {
+            let n: BigInt = n.parse().unwrap();
+            assert!(!eh_primo(&n, RODADAS_MILLER_RABIN), "{}", n);
+        }
+    }
+
+    #[test]
+    fn zero_rodadas_ainda_rejeitam_compostos_acima_de_2_64() {
+        // Produto de dois primos de Mersenne: nenhum fator pequeno para o crivo
+        let mersenne = |expoente: u32| (BigInt::one() << expoente) - 1;
+        let composto = mersenne(61) * mersenne(89);
+        assert!(crivo_primos_pequenos(&composto).is_none());
+        assert!(!eh_primo(&composto, 0));
+        assert!(!eh_primo_com_teste(
+            &composto,
+            TestePrimalidade::MillerRabin(0)
+        ));
+        assert!(eh_primo(&mersenne(127), 0));
+    }
+
+    #[test]
+    fn primo_gerado_tem_o_tamanho_pedido_e_os_dois_bits_altos() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for bits in [2, 3, 8, 17, 64, 65, 256, 512] {
+            let p = gerar_primo_com_rng(bits, &mut rng).unwrap();
+            let q = gerar_primo_com_rng(bits, &mut rng).unwrap();
+            for primo in [&p, &q] {
+                assert_eq!(primo.bits(), u64::from(bits), "{}", primo);
+                assert!(primo.bit(u64::from(bits - 1)) && primo.bit(u64::from(bits - 2)));
+                assert!(eh_primo(primo, RODADAS_MILLER_RABIN), "{}", primo);
+            }
+            assert_eq!((&p * &q).bits(), 2 * u64::from(bits));
+        }
+    }
+
+    #[test]
+    fn mesma_semente_gera_o_mesmo_primo() {
+        let gerar =
+            |semente| gerar_primo_com_rng(256, &mut StdRng::seed_from_u64(semente)).unwrap();
+        assert_eq!(gerar(2), gerar(2));
+        assert_ne!(gerar(2), gerar(3));
+    }
+
+    #[test]
+    fn menos_de_dois_bits_e_rejeitado() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for bits in [0, 1] {
+            assert_eq!(
+                gerar_primo_com_rng(bits, &mut rng),
+                Err(RsaError::InvalidBitLength(bits))
+            );
+        }
+    }
+}
@@ -0,0 +1,319 @@
+// --------------------------------------------------------
+// Fontes de Aleatoriedade: Entropia do Sistema e HMAC-DRBG
+// --------------------------------------------------------
+
+use crate::error::RsaError;
+use crate::hash::{hmac, FuncaoHash};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use std::marker::PhantomData;
+
+/// Fonte de aleatoriedade aceita pelas variantes `_com_rng` da geração de
+/// primos e de chaves, dos preenchimentos, do PSS e do cegamento.
+///
+/// Qualquer gerador criptográfico do `rand` (`thread_rng`, `OsRng`, `StdRng`)
+/// já é uma fonte; o crate fornece `EntropiaSistema` e `HmacDrbg`.
+pub trait RandomSource: RngCore + CryptoRng {}
+
+impl<T: RngCore + CryptoRng + ?Sized> RandomSource for T {}
+
+/// Entropia lida diretamente do sistema operacional (`getrandom`), sem
+/// estado no processo.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropiaSistema;
+
+impl RngCore for EntropiaSistema {
+    fn next_u32(&mut self) -> u32 {
+        OsRng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        OsRng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, destino: &mut [u8]) {
+        OsRng.fill_bytes(destino)
+    }
+
+    fn try_fill_bytes(&mut self, destino: &mut [u8]) -> Result<(), rand::Error> {
+        OsRng.try_fill_bytes(destino)
+    }
+}
+
+impl CryptoRng for EntropiaSistema {}
+
+/// Menor entrada de entropia aceita por `HmacDrbg`, em bytes: 256 bits, a
+/// maior força de segurança do SP 800-90A.
+pub const TAMANHO_MINIMO_ENTROPIA: usize = 32;
+
+/// Pedidos de `gerar` permitidos entre duas ressemeaduras (reseed_interval,
+/// o máximo da tabela 2 do SP 800-90A).
+pub const INTERVALO_RESSEMEADURA: u64 = 1 << 48;
+
+/// Maior pedido de `gerar`, em bytes (2^19 bits pela tabela 2).
+pub const TAMANHO_MAXIMO_PEDIDO: usize = 1 << 16;
+
+/// HMAC-DRBG do NIST SP 800-90A (seção 10.1.2), sobre o `hmac` do crate.
+///
+/// A mesma entropia, nonce e personalização produzem sempre a mesma
+/// sequência, o que permite reproduzir chaves e textos cifrados a partir de
+/// uma semente. Implementa `RngCore` e `SeedableRng`: um pedido maior que
+/// `TAMANHO_MAXIMO_PEDIDO` é atendido em vários `gerar` seguidos.
+#[derive(Clone)]
+pub struct HmacDrbg<H: FuncaoHash> {
+    chave: Vec<u8>,
+    valor: Vec<u8>,
+    contador_ressemeadura: u64,
+    hash: PhantomData<H>,
+}
+
+impl<H: FuncaoHash> HmacDrbg<H> {
+    /// HMAC_DRBG_Instantiate: K = 0x00…00, V = 0x01…01 e atualização com
+    /// entropia || nonce || personalização.
+    ///
+    /// Retorna `RsaError::RandomnessError` se `entropia` tiver menos de
+    /// `TAMANHO_MINIMO_ENTROPIA` bytes.
+    pub fn instanciar(
+        entropia: &[u8],
+        nonce: &[u8],
+        personalizacao: &[u8],
+    ) -> Result<Self, RsaError> {
+        if entropia.len() < TAMANHO_MINIMO_ENTROPIA {
+            return Err(RsaError::RandomnessError);
+        }
+
+        let mut drbg = HmacDrbg {
+            chave: vec![0x00; H::TAMANHO_SAIDA],
+            valor: vec![0x01; H::TAMANHO_SAIDA],
+            contador_ressemeadura: 1,
+            hash: PhantomData,
+        };
+        drbg.atualizar(&[entropia, nonce, personalizacao].concat());
+        Ok(drbg)
+    }
+
+    /// Instancia a partir de `TAMANHO_MINIMO_ENTROPIA` bytes de
+    /// `EntropiaSistema`, com `personalizacao` opcional.
+    pub fn a_partir_do_sistema(personalizacao: &[u8]) -> Result<Self, RsaError> {
+        let mut entropia = [0u8; TAMANHO_MINIMO_ENTROPIA];
+        let mut nonce = [0u8; TAMANHO_MINIMO_ENTROPIA / 2];
+        EntropiaSistema
+            .try_fill_bytes(&mut entropia)
+            .and_then(|_| EntropiaSistema.try_fill_bytes(&mut nonce))
+            .map_err(|_| RsaError::RandomnessError)?;
+        HmacDrbg::instanciar(&entropia, &nonce, personalizacao)
+    }
+
+    /// HMAC_DRBG_Reseed: mistura nova entropia ao estado e zera o contador.
+    ///
+    /// Retorna `RsaError::RandomnessError` se `entropia` tiver menos de
+    /// `TAMANHO_MINIMO_ENTROPIA` bytes.
+    pub fn ressemear(&mut self, entropia: &[u8], adicional: &[u8]) -> Result<(), RsaError> {
+        if entropia.len() < TAMANHO_MINIMO_ENTROPIA {
+            return Err(RsaError::RandomnessError);
+        }
+        self.atualizar(&[entropia, adicional].concat());
+        self.contador_ressemeadura = 1;
+        Ok(())
+    }
+
+    /// HMAC_DRBG_Generate: preenche `destino` com V = HMAC(K, V) sucessivos e
+    /// atualiza o estado com a entrada `adicional`.
+    ///
+    /// Retorna `RsaError::RandomnessError` se `destino` passar de
+    /// `TAMANHO_MAXIMO_PEDIDO` bytes ou se o gerador precisar ser ressemeado.
+    pub fn gerar(&mut self, destino: &mut [u8], adicional: &[u8]) -> Result<(), RsaError> {
+        if destino.len() > TAMANHO_MAXIMO_PEDIDO
+            || self.contador_ressemeadura > INTERVALO_RESSEMEADURA
+        {
+            return Err(RsaError::RandomnessError);
+        }
+        if !adicional.is_empty() {
+            self.atualizar(adicional);
+        }
+
+        for pedaco in destino.chunks_mut(H::TAMANHO_SAIDA) {
+            self.valor = hmac::<H>(&self.chave, &self.valor);
+            pedaco.copy_from_slice(&self.valor[..pedaco.len()]);
+        }
+
+        self.atualizar(adicional);
+        self.contador_ressemeadura += 1;
+        Ok(())
+    }
+
+    /// HMAC_DRBG_Update: K = HMAC(K, V || 0x00 || dados), V = HMAC(K, V) e,
+    /// se houver `dados`, uma segunda rodada com 0x01.
+    fn atualizar(&mut self, dados: &[u8]) {
+        for separador in [0x00u8, 0x01] {
+            self.chave = hmac::<H>(
+                &self.chave,
+                &[&self.valor[..], &[separador], dados].concat(),
+            );
+            self.valor = hmac::<H>(&self.chave, &self.valor);
+            if dados.is_empty() {
+                break;
+            }
+        }
+    }
+}
+
+impl<H: FuncaoHash> RngCore for HmacDrbg<H> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Entra em pânico se o gerador precisar ser ressemeado.
+    fn fill_bytes(&mut self, destino: &mut [u8]) {
+        self.try_fill_bytes(destino)
+            .expect("HMAC-DRBG precisa ser ressemeado")
+    }
+
+    fn try_fill_bytes(&mut self, destino: &mut [u8]) -> Result<(), rand::Error> {
+        for pedido in destino.chunks_mut(TAMANHO_MAXIMO_PEDIDO) {
+            self.gerar(pedido, &[]).map_err(rand::Error::new)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H: FuncaoHash> CryptoRng for HmacDrbg<H> {}
+
+impl<H: FuncaoHash> SeedableRng for HmacDrbg<H> {
+    type Seed = [u8; TAMANHO_MINIMO_ENTROPIA];
+
+    /// Usa a semente como entropia, sem nonce nem personalização.
+    fn from_seed(semente: Self::Seed) -> Self {
+        HmacDrbg::instanciar(&semente, &[], &[]).expect("a semente tem o tamanho mínimo")
+    }
+}
+
+/// Fonte que devolve os bytes dados, recomeçando do início ao esgotá-los;
+/// reproduz as sementes e salts fixos dos vetores de teste.
+#[cfg(test)]
+pub(crate) struct FonteFixa {
+    bytes: Vec<u8>,
+    posicao: usize,
+}
+
+#[cfg(test)]
+impl FonteFixa {
+    pub(crate) fn new(bytes: &[u8]) -> Self {
+        FonteFixa {
+            bytes: bytes.to_vec(),
+            posicao: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+impl RngCore for FonteFixa {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, destino: &mut [u8]) {
+        for byte in destino {
+            *byte = self.bytes[self.posicao];
+            self.posicao = (self.posicao + 1) % self.bytes.len();
+        }
+    }
+
+    fn try_fill_bytes(&mut self, destino: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(destino);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl CryptoRng for FonteFixa {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::hex_para_octetos;
+    use crate::hash::Sha256;
+    use crate::keys::{KeyPair, OpcoesGeracao};
+    use num_bigint::BigInt;
+
+    #[test]
+    fn vetor_cavp_hmac_drbg_sha256() {
+        // SP 800-90A, HMAC_DRBG com SHA-256, sem ressemeadura nem entradas
+        // adicionais: a saída conferida é a do segundo pedido de 128 bytes
+        let mut drbg = HmacDrbg::<Sha256>::instanciar(
+            &hex_para_octetos("ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488"),
+            &hex_para_octetos("659ba96c601dc69fc902940805ec0ca8"),
+            &[],
+        )
+        .unwrap();
+        let mut saida = [0u8; 128];
+        drbg.gerar(&mut saida, &[]).unwrap();
+        drbg.gerar(&mut saida, &[]).unwrap();
+        assert_eq!(
+            saida.to_vec(),
+            hex_para_octetos(
+                "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89\
+                 d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1\
+                 07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668\
+                 961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8"
+            )
+        );
+    }
+
+    #[test]
+    fn rejeita_entropia_curta_e_pedidos_grandes() {
+        assert_eq!(
+            HmacDrbg::<Sha256>::instanciar(&[0; TAMANHO_MINIMO_ENTROPIA - 1], &[], &[]).err(),
+            Some(RsaError::RandomnessError)
+        );
+
+        let mut drbg = HmacDrbg::<Sha256>::seed_from_u64(0);
+        let mut destino = vec![0u8; TAMANHO_MAXIMO_PEDIDO + 1];
+        assert_eq!(
+            drbg.gerar(&mut destino, &[]),
+            Err(RsaError::RandomnessError)
+        );
+        // Pela interface `RngCore`, o pedido é dividido em vários `gerar`
+        drbg.fill_bytes(&mut destino);
+    }
+
+    #[test]
+    fn semente_fixa_reproduz_a_chave() {
+        let gerar = |semente| {
+            let mut rng = HmacDrbg::<Sha256>::seed_from_u64(semente);
+            KeyPair::generate_com_rng(
+                512,
+                &BigInt::from(65537),
+                &OpcoesGeracao::default(),
+                &mut rng,
+            )
+            .unwrap()
+            .publica
+            .n
+        };
+        let esperado = BigInt::parse_bytes(
+            b"a45f89386895f418fcf71f2b5ac964fbfe3483eb06b90316e49d3df5e3099101\
+              ce92ba70bac77c21b6fc24b4e1fca2208a32706b21143c3a5806579f22269d05",
+            16,
+        );
+        assert_eq!(Some(gerar(1)), esperado);
+        assert_eq!(Some(gerar(1)), esperado);
+        assert_ne!(Some(gerar(2)), esperado);
+    }
+}
@@ -0,0 +1,43 @@
+// --------------------------------------------------------
+// Assinaturas Digitais
+// --------------------------------------------------------
+
+pub mod pkcs1v15;
+pub mod pss;
+
+use crate::encoding::{inteiro_para_octetos, octetos_para_inteiro, tamanho_em_bytes};
+use crate::error::RsaError;
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+
+/// RSASP1 sobre octetos: s = m^d mod n, com m = OS2IP(em); devolve k bytes.
+/// O cegamento, quando precisa de um novo r, o sorteia a partir de `rng`.
+fn assinar_octetos<R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    em: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let k = tamanho_em_bytes(chave.n());
+    let m = octetos_para_inteiro(em);
+    let s = chave.aplicar_expoente_privado_com_rng(&m, rng)?;
+    inteiro_para_octetos(&s, k)
+}
+
+/// RSAVP1 sobre octetos: m = s^e mod n, devolvido com `tamanho` bytes.
+///
+/// Qualquer falha (tamanho da assinatura diferente de k, s ≥ n ou m que não
+/// cabe em `tamanho` bytes) resulta em `RsaError::InvalidSignature`.
+fn verificar_octetos(
+    chave: &RsaPublicKey,
+    assinatura: &[u8],
+    tamanho: usize,
+) -> Result<Vec<u8>, RsaError> {
+    if assinatura.len() != tamanho_em_bytes(&chave.n) {
+        return Err(RsaError::InvalidSignature);
+    }
+    let s = octetos_para_inteiro(assinatura);
+    let m = chave
+        .aplicar_expoente_publico(&s)
+        .map_err(|_| RsaError::InvalidSignature)?;
+    inteiro_para_octetos(&m, tamanho).map_err(|_| RsaError::InvalidSignature)
+}
@@ -0,0 +1,232 @@
+// --------------------------------------------------------
+// RSASSA-PKCS1-v1_5 (RFC 8017, seção 8.2)
+// --------------------------------------------------------
+
+use super::{assinar_octetos, verificar_octetos};
+use crate::encoding::tamanho_em_bytes;
+use crate::error::RsaError;
+use crate::hash::{FuncaoHash, Sha224, Sha256, Sha384, Sha512};
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+
+/// Função de hash com o prefixo DER da estrutura DigestInfo
+/// (RFC 8017, seção 9.2, nota 1), que identifica o algoritmo na assinatura.
+pub trait HashComDigestInfo: FuncaoHash {
+    /// DigestInfo sem o resumo: SEQUENCE { AlgorithmIdentifier, OCTET STRING }.
+    const PREFIXO_DIGEST_INFO: &'static [u8];
+}
+
+impl HashComDigestInfo for Sha224 {
+    const PREFIXO_DIGEST_INFO: &'static [u8] = &[
+        0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04,
+        0x05, 0x00, 0x04, 0x1c,
+    ];
+}
+
+impl HashComDigestInfo for Sha256 {
+    const PREFIXO_DIGEST_INFO: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        0x05, 0x00, 0x04, 0x20,
+    ];
+}
+
+impl HashComDigestInfo for Sha384 {
+    const PREFIXO_DIGEST_INFO: &'static [u8] = &[
+        0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02,
+        0x05, 0x00, 0x04, 0x30,
+    ];
+}
+
+impl HashComDigestInfo for Sha512 {
+    const PREFIXO_DIGEST_INFO: &'static [u8] = &[
+        0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+        0x05, 0x00, 0x04, 0x40,
+    ];
+}
+
+/// Tamanho mínimo da sequência de bytes 0xff.
+const TAMANHO_MINIMO_PS: usize = 8;
+
+/// Assina `mensagem` com RSASSA-PKCS1-v1_5 (determinístico).
+pub fn assinar<H: HashComDigestInfo>(
+    chave: &RsaPrivateKey,
+    mensagem: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    assinar_com_rng::<H, _>(chave, mensagem, &mut rand::thread_rng())
+}
+
+/// Variante de `assinar` que sorteia o cegamento a partir de `rng`; a
+/// assinatura produzida não depende dele.
+pub fn assinar_com_rng<H: HashComDigestInfo, R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    mensagem: &[u8],
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    let em = codificar::<H>(mensagem, tamanho_em_bytes(chave.n()))?;
+    assinar_octetos(chave, &em, rng)
+}
+
+/// Verifica uma assinatura RSASSA-PKCS1-v1_5.
+///
+/// A codificação esperada é recalculada e comparada byte a byte com a obtida,
+/// em vez de interpretar o DigestInfo recebido. Assim não há tolerância a
+/// bytes extras após o resumo nem a parâmetros ASN.1 alterados, que é o que
+/// permite a falsificação de Bleichenbacher (2006) com expoente 3.
+pub fn verificar<H: HashComDigestInfo>(
+    chave: &RsaPublicKey,
+    mensagem: &[u8],
+    assinatura: &[u8],
+) -> Result<(), RsaError> {
+    let k = tamanho_em_bytes(&chave.n);
+    let em = verificar_octetos(chave, assinatura, k)?;
+    let esperado = codificar::<H>(mensagem, k).map_err(|_| RsaError::InvalidSignature)?;
+
+    if em != esperado {
+        return Err(RsaError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// EMSA-PKCS1-v1_5: EM = 0x00 || 0x01 || PS (0xff) || 0x00 || DigestInfo.
+///
+/// Retorna `RsaError::MessageTooLong` se `k` não comportar o DigestInfo e 11 bytes.
+pub fn codificar<H: HashComDigestInfo>(mensagem: &[u8], k: usize) -> Result<Vec<u8>, RsaError> {
+    let mut t = H::PREFIXO_DIGEST_INFO.to_vec();
+    t.extend(H::digest(mensagem));
+    if k < t.len() + TAMANHO_MINIMO_PS + 3 {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    let mut em = vec![0x00, 0x01];
+    em.resize(k - t.len() - 1, 0xff);
+    em.push(0x00);
+    em.extend_from_slice(&t);
+    Ok(em)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{chave_de_teste_1024, hex_para_octetos};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Assina uma codificação arbitrária, como faria um falsificador.
+    fn assinar_em(chave: &RsaPrivateKey, em: &[u8]) -> Vec<u8> {
+        assinar_octetos(chave, em, &mut StdRng::seed_from_u64(11)).unwrap()
+    }
+
+    #[test]
+    fn vetor_conhecido_com_sha256() {
+        // Gerado e verificado com a biblioteca `cryptography` do Python
+        let mensagem = b"RSASSA-PKCS1-v1_5 com SHA-256";
+        let esperada = hex_para_octetos(
+            "9c168516484b096724823ca98b4ac07975ebe8b6caaaa73817ba761c5cc0d252\
+             e0b3f4892a4e2ff14b32cd6bb7bc198ad7d03413dc6d336495b7ed5816eaef5c\
+             70a943bbd5ee94385bee6b7ee197c5e2b38333523ed6e457a62901214b538a43\
+             a419289d09a6007d71f4d4f6e58ea59f16d4c80a1ca91e00ca725d5144dc6e01",
+        );
+        let chave = chave_de_teste_1024();
+
+        assert_eq!(assinar::<Sha256>(&chave, mensagem).unwrap(), esperada);
+        let publica = chave.chave_publica();
+        assert_eq!(verificar::<Sha256>(&publica, mensagem, &esperada), Ok(()));
+        assert_eq!(
+            verificar::<Sha256>(&publica, b"outra mensagem", &esperada),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    fn ida_e_volta<H: HashComDigestInfo>(chave: &RsaPrivateKey) {
+        let mensagem = b"ida e volta";
+        let assinatura = assinar::<H>(chave, mensagem).unwrap();
+        let publica = chave.chave_publica();
+        assert_eq!(verificar::<H>(&publica, mensagem, &assinatura), Ok(()));
+        assert_eq!(
+            verificar::<H>(&publica, &mensagem[1..], &assinatura),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn assina_e_verifica_com_cada_hash() {
+        let chave = chave_de_teste_1024();
+        ida_e_volta::<Sha224>(&chave);
+        ida_e_volta::<Sha256>(&chave);
+        ida_e_volta::<Sha384>(&chave);
+        ida_e_volta::<Sha512>(&chave);
+
+        // O identificador do algoritmo faz parte da codificação
+        let assinatura = assinar::<Sha256>(&chave, b"m").unwrap();
+        assert_eq!(
+            verificar::<Sha512>(&chave.chave_publica(), b"m", &assinatura),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn codificacoes_malformadas_sao_rejeitadas() {
+        let chave = chave_de_teste_1024();
+        let publica = chave.chave_publica();
+        let k = tamanho_em_bytes(chave.n());
+        let mensagem = b"mensagem";
+        let em = codificar::<Sha256>(mensagem, k).unwrap();
+        let inicio_t = k - Sha256::PREFIXO_DIGEST_INFO.len() - 32;
+
+        // Bytes extras após o resumo, com o PS encurtado para manter o tamanho
+        let mut lixo = em.clone();
+        lixo.drain(2..4);
+        lixo.extend_from_slice(&[0xde, 0xad]);
+
+        // Parâmetros do AlgorithmIdentifier trocados (NULL → OCTET STRING vazia)
+        let mut parametros = em.clone();
+        parametros[inicio_t + 15] = 0x04;
+
+        // PS com apenas 7 bytes seguido do separador
+        let mut ps_curto = em.clone();
+        ps_curto[2 + TAMANHO_MINIMO_PS - 1] = 0x00;
+
+        // OID do SHA-512 com o resumo SHA-256
+        let mut oid = em.clone();
+        oid[inicio_t + 14] = 0x03;
+
+        // Primeiro byte do PS diferente de 0xff
+        let mut ps_alterado = em.clone();
+        ps_alterado[2] = 0xfe;
+
+        assert_eq!(
+            verificar::<Sha256>(&publica, mensagem, &assinar_em(&chave, &em)),
+            Ok(())
+        );
+        for falsa in [lixo, parametros, ps_curto, oid, ps_alterado] {
+            assert_eq!(falsa.len(), k);
+            assert_eq!(
+                verificar::<Sha256>(&publica, mensagem, &assinar_em(&chave, &falsa)),
+                Err(RsaError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn assinatura_de_tamanho_errado_e_rejeitada() {
+        let chave = chave_de_teste_1024();
+        let publica = chave.chave_publica();
+        let assinatura = assinar::<Sha256>(&chave, b"m").unwrap();
+
+        let mut longa = vec![0x00];
+        longa.extend_from_slice(&assinatura);
+        for errada in [&assinatura[1..], &longa[..], &[][..]] {
+            assert_eq!(
+                verificar::<Sha256>(&publica, b"m", errada),
+                Err(RsaError::InvalidSignature)
+            );
+        }
+    }
+
+    #[test]
+    fn modulo_pequeno_demais_e_rejeitado() {
+        // 19 bytes de DigestInfo + 32 de resumo + 11 de preenchimento
+        assert!(codificar::<Sha256>(b"m", 62).is_ok());
+        assert_eq!(codificar::<Sha256>(b"m", 61), Err(RsaError::MessageTooLong));
+    }
+}
@@ -0,0 +1,323 @@
+// --------------------------------------------------------
+// RSASSA-PSS (RFC 8017, seção 8.1)
+// --------------------------------------------------------
+
+use super::{assinar_octetos, verificar_octetos};
+use crate::error::RsaError;
+use crate::hash::{mgf1, FuncaoHash};
+use crate::keys::{RsaPrivateKey, RsaPublicKey};
+use crate::random::RandomSource;
+use num_bigint::BigInt;
+use num_traits::One;
+use rand::Rng;
+
+/// Assina `mensagem` com RSASSA-PSS, usando `H` como hash da mensagem e na
+/// MGF1, com um sal aleatório de `tamanho_sal` bytes.
+pub fn assinar<H: FuncaoHash>(
+    chave: &RsaPrivateKey,
+    mensagem: &[u8],
+    tamanho_sal: usize,
+) -> Result<Vec<u8>, RsaError> {
+    assinar_com_rng::<H, _>(chave, mensagem, tamanho_sal, &mut rand::thread_rng())
+}
+
+/// Variante de `assinar` que sorteia o sal e o cegamento a partir de `rng`.
+///
+/// Retorna `RsaError::InvalidKey` se `n <= 1`.
+pub fn assinar_com_rng<H: FuncaoHash, R: RandomSource + ?Sized>(
+    chave: &RsaPrivateKey,
+    mensagem: &[u8],
+    tamanho_sal: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, RsaError> {
+    if *chave.n() <= BigInt::one() {
+        return Err(RsaError::InvalidKey);
+    }
+    let mut sal = vec![0u8; tamanho_sal];
+    rng.fill(sal.as_mut_slice());
+
+    let em_bits = chave.n().bits() as usize - 1;
+    let em = codificar::<H>(&H::digest(mensagem), &sal, em_bits)?;
+    assinar_octetos(chave, &em, rng)
+}
+
+/// Verifica uma assinatura RSASSA-PSS produzida com o mesmo `H` e `tamanho_sal`.
+///
+/// Retorna `RsaError::InvalidSignature` se a assinatura não conferir ou se
+/// `n <= 1`.
+pub fn verificar<H: FuncaoHash>(
+    chave: &RsaPublicKey,
+    mensagem: &[u8],
+    assinatura: &[u8],
+    tamanho_sal: usize,
+) -> Result<(), RsaError> {
+    if chave.n <= BigInt::one() {
+        return Err(RsaError::InvalidSignature);
+    }
+    let em_bits = chave.n.bits() as usize - 1;
+    let em = verificar_octetos(chave, assinatura, em_bits.div_ceil(8))?;
+    verificar_codificacao::<H>(&H::digest(mensagem), &em, em_bits, tamanho_sal)
+}
+
+/// EMSA-PSS-ENCODE: EM = maskedDB || H' || 0xbc, com
+/// H' = H(0x00 × 8 || mHash || sal) e DB = PS || 0x01 || sal.
+///
+/// Retorna `RsaError::MessageTooLong` se o módulo não comportar o hash e o sal.
+pub fn codificar<H: FuncaoHash>(
+    hash_mensagem: &[u8],
+    sal: &[u8],
+    em_bits: usize,
+) -> Result<Vec<u8>, RsaError> {
+    let h_len = H::TAMANHO_SAIDA;
+    let em_len = em_bits.div_ceil(8);
+    if em_len < h_len + sal.len() + 2 {
+        return Err(RsaError::MessageTooLong);
+    }
+
+    let h = hash_com_sal::<H>(hash_mensagem, sal);
+
+    let mut db = vec![0u8; em_len - sal.len() - h_len - 2];
+    db.push(0x01);
+    db.extend_from_slice(sal);
+    for (byte, m) in db.iter_mut().zip(mgf1::<H>(&h, em_len - h_len - 1)) {
+        *byte ^= m;
+    }
+    db[0] &= mascara_bits_altos(em_len, em_bits);
+
+    let mut em = db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// EMSA-PSS-VERIFY (RFC 8017, seção 9.1.2).
+pub fn verificar_codificacao<H: FuncaoHash>(
+    hash_mensagem: &[u8],
+    em: &[u8],
+    em_bits: usize,
+    tamanho_sal: usize,
+) -> Result<(), RsaError> {
+    let h_len = H::TAMANHO_SAIDA;
+    let em_len = em_bits.div_ceil(8);
+    if em.len() != em_len || em_len < h_len + tamanho_sal + 2 || em[em_len - 1] != 0xbc {
+        return Err(RsaError::InvalidSignature);
+    }
+
+    let (db_mascarado, resto) = em.split_at(em_len - h_len - 1);
+    let h = &resto[..h_len];
+    let mascara = mascara_bits_altos(em_len, em_bits);
+    if db_mascarado[0] & !mascara != 0 {
+        return Err(RsaError::InvalidSignature);
+    }
+
+    let mut db: Vec<u8> = db_mascarado
+        .iter()
+        .zip(mgf1::<H>(h, db_mascarado.len()))
+        .map(|(byte, m)| byte ^ m)
+        .collect();
+    db[0] &= mascara;
+
+    // DB = PS (zeros) || 0x01 || sal
+    let tamanho_ps = em_len - h_len - tamanho_sal - 2;
+    if db[..tamanho_ps].iter().any(|&byte| byte != 0x00) || db[tamanho_ps] != 0x01 {
+        return Err(RsaError::InvalidSignature);
+    }
+    let sal = &db[db.len() - tamanho_sal..];
+
+    if hash_com_sal::<H>(hash_mensagem, sal) != h {
+        return Err(RsaError::InvalidSignature);
+    }
+    Ok(())
+}
+
+/// H' = H(0x00 × 8 || mHash || sal).
+fn hash_com_sal<H: FuncaoHash>(hash_mensagem: &[u8], sal: &[u8]) -> Vec<u8> {
+    let mut hash = H::new();
+    hash.update(&[0u8; 8]);
+    hash.update(hash_mensagem);
+    hash.update(sal);
+    hash.finalize()
+}
+
+/// Máscara que zera os 8·emLen − emBits bits mais altos do primeiro byte.
+fn mascara_bits_altos(em_len: usize, em_bits: usize) -> u8 {
+    0xff >> (8 * em_len - em_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::{
+        chave_de_primos_hex, chave_de_teste_1024, hex_para_octetos, tamanho_em_bytes,
+    };
+    use crate::hash::{Sha1, Sha256, Sha384, Sha512};
+    use crate::random::{FonteFixa, HmacDrbg};
+    use rand::{RngCore, SeedableRng};
+
+    #[test]
+    fn vetor_da_rfc_8017_com_sal_fixo() {
+        // Exemplo pss-int.txt do PKCS #1 v2.1 (SHA-1, sal de 20 bytes)
+        let chave = chave_de_primos_hex(
+            "d17f655bf27c8b16d35462c905cc04a26f37e2a67fa9c0ce0dced472394a0df7\
+             43fe7f929e378efdb368eddff453cf007af6d948e0ade757371f8a711e278f6b",
+            "c6d92b6fee7414d1358ce1546fb62987530b90bd15e0f14963a5e2635adb6934\
+             7ec0c01b2ab1763fd8ac1a592fb22757463a982425bb97a3a437c5bf86d03f2f",
+            65537,
+        );
+        let mensagem = hex_para_octetos(
+            "859eef2fd78aca00308bdc471193bf55bf9d78db8f8a672b484634f3c9c26e64\
+             78ae10260fe0dd8c082e53a5293af2173cd50c6d5d354febf78b26021c25c027\
+             12e78cd4694c9f469777e451e7f8e9e04cd3739c6bbfedae487fb55644e9ca74\
+             ff77a53cb729802f6ed4a5ffa8ba159890fc",
+        );
+        let sal = hex_para_octetos("e3b5d5d002c1bce50c2b65ef88a188d83bce7e61");
+        let esperada = hex_para_octetos(
+            "8daa627d3de7595d63056c7ec659e54406f10610128baae821c8b2a0f3936d54\
+             dc3bdce46689f6b7951bb18e840542769718d5715d210d85efbb596192032c42\
+             be4c29972c856275eb6d5a45f05f51876fc6743deddd28caec9bb30ea99e02c3\
+             488269604fe497f74ccd7c7fca1671897123cbd30def5d54a2b5536ad90a747e",
+        );
+
+        let assinatura =
+            assinar_com_rng::<Sha1, _>(&chave, &mensagem, sal.len(), &mut FonteFixa::new(&sal))
+                .unwrap();
+        assert_eq!(assinatura, esperada);
+
+        // O clone começa sem par de cegamento, que então também sai do rng dado
+        let mut rng = HmacDrbg::<Sha256>::seed_from_u64(10);
+        let mut so_sal = rng.clone();
+        so_sal.next_u32();
+        assinar_com_rng::<Sha1, _>(&chave.clone(), &mensagem, 4, &mut rng).unwrap();
+        assert_ne!(rng.next_u64(), so_sal.next_u64());
+
+        let publica = chave.chave_publica();
+        assert_eq!(
+            verificar::<Sha1>(&publica, &mensagem, &esperada, 20),
+            Ok(())
+        );
+        assert_eq!(
+            verificar::<Sha1>(&publica, &mensagem[1..], &esperada, 20),
+            Err(RsaError::InvalidSignature)
+        );
+        assert_eq!(
+            verificar::<Sha1>(&publica, &mensagem, &esperada, 19),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    // Com um módulo de 1025 bits, emBits = 1024 é múltiplo de 8 e o EM tem
+    // k − 1 = 128 bytes. A primeira assinatura foi produzida pela biblioteca
+    // `cryptography` do Python; a segunda, com o sal fixo, foi verificada por ela.
+    #[test]
+    fn modulo_com_em_bits_multiplo_de_8() {
+        let chave = chave_de_primos_hex(
+            "19977cd77d832ca041cfbe7be259bc5c4157f4da78252e2bed3e55910170a8da\
+             ea2dfb48eb7cadeb1893222f5b433218f9f919ac52efb22d546d4ddfc861e1efd",
+            "fda95bb95c6d2c69873a1ebf0eaa89595891fdde8b84cab353576a862485c8d6\
+             02d007e34ab5999f0ef311cf30192d77d9f9f7b724542ef8bd8360cd30a3e32b",
+            65537,
+        );
+        let publica = chave.chave_publica();
+        assert_eq!(chave.n().bits(), 1025);
+        assert_eq!(tamanho_em_bytes(chave.n()), 129);
+        let mensagem = b"emBits multiplo de 8";
+
+        let externa = hex_para_octetos(
+            "006880f8118272d6498b6652f0d68bda5ba21e8b08e5fe161556ec9718319fb7\
+             47b09dbcc3929f0064f416794a93a91ba0b5c025d2c306408b69beaa58de7fb7\
+             ee5e971398fe1deaf8e73543cc87fdecc8b31f6886cd64076dc5e56dfe975af2\
+             67b7edcceb525e031977d3ac21d83479a4b2b5237a6b65a268c9f65390f2de56ae",
+        );
+        assert_eq!(
+            verificar::<Sha256>(&publica, mensagem, &externa, 32),
+            Ok(())
+        );
+
+        let sal: Vec<u8> = (0x20..0x40).collect();
+        let em = codificar::<Sha256>(&Sha256::digest(mensagem), &sal, 1024).unwrap();
+        assert_eq!(em.len(), 128);
+        let assinatura =
+            assinar_com_rng::<Sha256, _>(&chave, mensagem, sal.len(), &mut FonteFixa::new(&sal))
+                .unwrap();
+        assert_eq!(
+            assinatura,
+            hex_para_octetos(
+                "00efe1e9722a1908c4e35f501b22493facb1d4cd9be5dbc44804524aa35307c6\
+                 3d3dfee60221bf8dfc47655bafde7539f395822236aa7805137cbb9485137347\
+                 00494d03f9a17a2d035fa2c070453af11b1cd90fcbb3df7987885c0191a47463\
+                 f06a0941cf50bb26df15e0965582c98f5578615451d99615b8e82d8d7240cf9775",
+            )
+        );
+        assert_eq!(
+            verificar::<Sha256>(&publica, mensagem, &assinatura, 32),
+            Ok(())
+        );
+
+        let mut alterada = assinatura.clone();
+        alterada[128] ^= 0x01;
+        assert_eq!(
+            verificar::<Sha256>(&publica, mensagem, &alterada, 32),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    fn ida_e_volta<H: FuncaoHash>(chave: &RsaPrivateKey, tamanho_sal: usize) {
+        let mensagem = b"ida e volta";
+        let mut rng = HmacDrbg::<Sha256>::seed_from_u64(10);
+        let assinatura = assinar_com_rng::<H, _>(chave, mensagem, tamanho_sal, &mut rng).unwrap();
+        let publica = chave.chave_publica();
+        assert_eq!(
+            verificar::<H>(&publica, mensagem, &assinatura, tamanho_sal),
+            Ok(())
+        );
+        assert_eq!(
+            verificar::<H>(&publica, &mensagem[1..], &assinatura, tamanho_sal),
+            Err(RsaError::InvalidSignature)
+        );
+        assert_eq!(
+            verificar::<H>(&publica, mensagem, &assinatura, tamanho_sal + 1),
+            Err(RsaError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn assina_e_verifica_com_sha384_e_sha512() {
+        let chave = chave_de_teste_1024();
+        for tamanho_sal in [0, Sha384::TAMANHO_SAIDA] {
+            ida_e_volta::<Sha384>(&chave, tamanho_sal);
+        }
+        ida_e_volta::<Sha512>(&chave, 0);
+
+        // MGF1 e resumo precisam ser os mesmos dos dois lados
+        let assinatura = assinar::<Sha384>(&chave, b"m", 0).unwrap();
+        assert_eq!(
+            verificar::<Sha512>(&chave.chave_publica(), b"m", &assinatura, 0),
+            Err(RsaError::InvalidSignature)
+        );
+
+        // emLen = 128 bytes não comporta 64 de resumo + 64 de sal + 2
+        assert_eq!(
+            assinar::<Sha512>(&chave, b"m", Sha512::TAMANHO_SAIDA),
+            Err(RsaError::MessageTooLong)
+        );
+        ida_e_volta::<Sha512>(&chave, 128 - Sha512::TAMANHO_SAIDA - 2);
+    }
+
+    #[test]
+    fn modulo_menor_ou_igual_a_1_e_rejeitado_na_verificacao() {
+        for n in [-1, 0, 1] {
+            let publica = RsaPublicKey {
+                n: BigInt::from(n),
+                e: BigInt::from(65537),
+            };
+            assert_eq!(
+                verificar::<Sha256>(&publica, b"m", &[], 0),
+                Err(RsaError::InvalidSignature)
+            );
+            assert_eq!(
+                verificar::<Sha256>(&publica, b"m", &[0x01], 0),
+                Err(RsaError::InvalidSignature)
+            );
+        }
+    }
+}